@@ -14,15 +14,26 @@
 //!
 //! ## Invariants
 //! - A badge_id can only be defined once (`define_badge` is idempotent-guarded).
-//! - A user can only hold each badge once (duplicate awards are rejected).
+//! - A user holds each badge at most once in their holdings list. Duplicate
+//!   awards are rejected, except for stackable badges, which count repeats in
+//!   `BadgeStack` instead.
 //! - `award_badge` requires the badge to be defined and the user not to already
-//!   hold it, in that order, with no TOCTOU gap.
+//!   hold it (unless stackable), in that order, with no TOCTOU gap.
 #![no_std]
 #![allow(unexpected_cfgs)]
 
+#[cfg(all(
+    feature = "test-bypass-auth",
+    any(target_family = "wasm", not(debug_assertions))
+))]
+compile_error!(
+    "`test-bypass-auth` disables authorization and is for native debug test builds only"
+);
+
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, vec, Address, BytesN, Env,
-    String, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    token::TokenClient, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol,
+    TryFromVal, Val, Vec,
 };
 
 // ---------------------------------------------------------------------------
@@ -33,6 +44,34 @@ use soroban_sdk::{
 /// Bumped on every write so badge and user data never expire.
 pub const PERSISTENT_BUMP_LEDGERS: u32 = 518_400;
 
+/// Largest accepted `reward_token_decimals`.
+pub const MAX_REWARD_TOKEN_DECIMALS: u32 = 18;
+
+/// Longest accepted contract name, in bytes.
+pub const MAX_CONTRACT_NAME_LEN: u32 = 32;
+
+/// Current storage layout version.
+///
+/// - 1: original layout, `BadgeDefinition { criteria_hash, reward }`
+///   (`BadgeDefinitionV1`). Deployments without `SchemaVersion` are on 1.
+/// - 2: `BadgeDefinition` with award policies, supply, and verifier fields
+///   (`BadgeDefinitionV2`).
+/// - 3: adds `BadgeDefinition::name` (`BadgeDefinitionV3`).
+/// - 4: adds `BadgeDefinition::reward_token` (`BadgeDefinitionV4`).
+/// - 5: adds `BadgeDefinition::scope` (`BadgeDefinitionV5`).
+/// - 6: adds `BadgeDefinition::stackable`.
+pub const SCHEMA_VERSION: u32 = 6;
+
+/// Longest accepted badge name, in bytes.
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// Upper bound on `limit` for paginated queries.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// All-zero contract address used as `BadgeAwarded::reward_token` when no
+/// token will be paid.
+pub const NO_REWARD_TOKEN: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+
 // ---------------------------------------------------------------------------
 // Error Types
 // ---------------------------------------------------------------------------
@@ -42,12 +81,36 @@ pub const PERSISTENT_BUMP_LEDGERS: u32 = 518_400;
 #[repr(u32)]
 pub enum Error {
     AlreadyInitialized = 1,
-    NotInitialized     = 2,
-    NotAuthorized      = 3,
-    BadgeNotFound      = 4,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    BadgeNotFound = 4,
     BadgeAlreadyExists = 5,
     BadgeAlreadyAwarded = 6,
-    InvalidInput       = 7,
+    InvalidInput = 7,
+    UserBadgeLimit = 8,
+    NotEvaluated = 9,
+    AwardWindowClosed = 10,
+    AdminRenounced = 11,
+    PrerequisitesNotMet = 12,
+    InvalidProof = 13,
+    InvariantViolation = 14,
+    NoPendingOffer = 15,
+    CooldownActive = 16,
+    SupplyExhausted = 17,
+    NotEligible = 18,
+    SchemaVersionMismatch = 19,
+    NothingToClaim = 20,
+    RewardTokenNotSet = 21,
+    Overflow = 22,
+    BadgeLocked = 23,
+    EpochClosed = 24,
+    IncompatibleReward = 25,
+    PlayerNotJoined = 26,
+    TournamentContractNotSet = 27,
+    InsufficientFunds = 28,
+    ContractPaused = 29,
+    AlreadyPaused = 30,
+    NotPaused = 31,
 }
 
 // ---------------------------------------------------------------------------
@@ -56,7 +119,10 @@ pub enum Error {
 
 /// Discriminants for all storage keys.
 ///
-/// Instance keys (Admin, RewardContract): contract config, one ledger entry.
+/// Instance keys (Admin, RewardContract, MaxBadgesPerUser, AdminRenounced,
+/// RewardTokenDecimals, VerboseStorageEvents, SchemaVersion, RewardToken,
+/// BatchEventMode, SettlementMode, Paused):
+/// contract config, one ledger entry.
 /// Persistent keys (Badge, UserBadges): per-badge definitions and per-user
 /// badge lists, each with their own TTL.
 #[contracttype]
@@ -64,6 +130,42 @@ pub enum DataKey {
     // --- instance() ---
     Admin,
     RewardContract,
+    /// Maximum number of badges a single user may hold. 0 = unlimited.
+    MaxBadgesPerUser,
+    /// Set once the admin has been burned; the contract is then immutable.
+    AdminRenounced,
+    /// Decimals of the reward token, for rendering raw `reward` amounts.
+    RewardTokenDecimals,
+    /// When set, every persistent TTL bump emits `StorageBumped`.
+    VerboseStorageEvents,
+    /// Storage layout version; see `SCHEMA_VERSION`.
+    SchemaVersion,
+    /// Token paid out by `claim_rewards`.
+    RewardToken,
+    /// Which events `award_badge_batch` emits; see `BatchEventMode`.
+    BatchEventMode,
+    /// How rewards are paid; see `SettlementMode`.
+    SettlementMode,
+    /// Human-readable label of this instance, fixed at `init`.
+    ContractName,
+    /// Number of awards ever made, across all badges and users.
+    AwardOrdinal,
+    /// Current season; every lower epoch is closed.
+    CurrentEpoch,
+    /// `abi_version` the payout token must report before atomic payouts.
+    ExpectedRewardAbi,
+    /// Tournament contract consulted for tournament-scoped badges.
+    TournamentContract,
+    /// Which events fire; see `EventLevel`.
+    EventLevel,
+    /// Present while awards are paused.
+    Paused,
+    /// Number of defined badges, reported by `storage_stats`.
+    BadgeCount,
+    /// Number of defined meta badges, reported by `storage_stats`.
+    MetaBadgeCount,
+    /// Current holdings across all users, reported by `storage_stats`.
+    HoldingCount,
     // --- persistent() ---
     /// Badge definition keyed by badge_id (u64).
     Badge(u64),
@@ -71,21 +173,273 @@ pub enum DataKey {
     UserBadges(Address),
     /// Human-readable metadata for a badge, keyed by badge_id.
     BadgeMeta(u64),
+    /// Reverse index: badge_ids defined with a given criteria hash.
+    HashToBadges(BytesN<32>),
+    /// Ledger at which a user was last evaluated for a badge.
+    Evaluated(Address, u64),
+    /// Prerequisite badge_ids for a meta badge, keyed by the meta badge_id.
+    MetaBadge(u64),
+    /// Merkle root of addresses eligible to self-claim a badge.
+    ClaimRoot(u64),
+    /// Every defined badge_id, in definition order.
+    BadgeIndex,
+    /// Badge offered to a user and awaiting acceptance.
+    PendingBadge(Address, u64),
+    /// `(award_ledger, cooldown_ledgers)` of a user's latest award in a
+    /// badge family.
+    FamilyAward(Address, u32),
+    /// Number of users holding a badge, keyed by badge_id.
+    HolderCount(u64),
+    /// Number of badges awarded by an issuer, keyed by issuer Address.
+    IssuerAwardCount(Address),
+    /// Details of a single award, keyed by (user, badge_id).
+    AwardRecord(Address, u64),
+    /// Rewards earned by a user and not yet claimed.
+    Claimable(Address),
+    /// 64-byte criteria commitment of a badge defined by `define_badge_64`.
+    Badge64(u64),
+    /// Unclaimed rewards of a user in a badge's `reward_token` override,
+    /// keyed by (user, token).
+    ClaimableIn(Address, Address),
+    /// `(cliff_ledgers, duration_ledgers)` vesting schedule of a badge's
+    /// reward, keyed by badge_id.
+    BadgeVesting(u64),
+    /// Vesting reward of one award, keyed by (user, badge_id).
+    Vesting(Address, u64),
+    /// Ledger of a user's latest award.
+    LastActive(Address),
+    /// Tournament an award was earned in, keyed by (user, badge_id).
+    AwardSource(Address, u64),
+    /// Position of an award in the global issuance order, keyed by
+    /// (user, badge_id).
+    AwardOrdinalOf(Address, u64),
+    /// Present while a user hides their holdings from public queries.
+    Private(Address),
+    /// Reward held back for a pending offer until it is accepted, keyed by
+    /// (user, badge_id).
+    PendingReward(Address, u64),
+    /// Badge IDs offered to a user and awaiting acceptance, in offer order.
+    UserPending(Address),
+    /// Approximate top `MAX_PAGE_SIZE` `(user, points)` pairs, highest first.
+    PointsLeaderboard,
+    /// Times a user has earned a stackable badge, keyed by (user, badge_id).
+    BadgeStack(Address, u64),
+    /// Contract notified via `on_badge_awarded` after each award of a badge.
+    BadgeSubscriber(u64),
+    /// Number of pending offers of a badge, keyed by badge_id.
+    OfferCount(u64),
 }
 
 /// Definition of a badge, stored on-chain.
 ///
-/// `criteria_hash` is a 32-byte SHA-256 hash of the off-chain criteria
-/// document, providing a tamper-evident commitment without on-chain verbosity.
+/// `criteria_hash` is a 32-byte hash (SHA-256 or Keccak-256, per `hash_algo`)
+/// of the off-chain criteria document, providing a tamper-evident commitment
+/// without on-chain verbosity.
 /// `reward` is an optional i128 amount to disburse via the reward contract
-/// when the badge is awarded; 0 means no reward.
+/// when the badge is awarded; 0 means no reward. The remaining fields are
+/// optional award policies; `BadgeDefinition::new` leaves them all disabled.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BadgeDefinition {
-    /// SHA-256 hash of the off-chain criteria specification (32 bytes).
+    /// Hash of the off-chain criteria specification (32 bytes).
     pub criteria_hash: BytesN<32>,
+    /// Algorithm that produced `criteria_hash`.
+    pub hash_algo: HashAlgo,
     /// Token amount paid via `reward_contract` when badge is awarded. 0 = none.
     pub reward: i128,
+    /// When `true`, `award_badge` requires a prior `evaluate_user` call.
+    pub require_evaluation: bool,
+    /// Ledger range in which the badge may be awarded.
+    pub award_window: AwardWindow,
+    /// Group of related badges sharing a cooldown. 0 = no family.
+    pub badge_family: u32,
+    /// Ledgers a user must wait after earning this badge before earning
+    /// another badge of the same family. Requires a non-zero `badge_family`.
+    pub cooldown_ledgers: u32,
+    /// Maximum number of holders. `None` = unlimited.
+    pub max_supply: Option<u32>,
+    /// External contract consulted by `award_if_eligible`. `None` = award
+    /// directly.
+    pub verifier: Option<Address>,
+    /// Human-readable name, 1 to `MAX_NAME_LEN` bytes. `None` = unnamed.
+    pub name: Option<String>,
+    /// Token `reward` is paid in. `None` = the contract's `RewardToken`.
+    pub reward_token: Option<Address>,
+    /// Where the badge may be earned.
+    pub scope: BadgeScope,
+    /// Let a holder earn the badge again, counting each award in
+    /// `BadgeStack` instead of rejecting it as a duplicate. Every award pays
+    /// `reward`. Repeats take no holdings slot, so neither `max_supply` nor
+    /// the per-user badge cap limits them.
+    pub stackable: bool,
+}
+
+/// Context a badge belongs to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BadgeScope {
+    /// Earnable by anyone.
+    Global,
+    /// Only earnable by players who joined the given tournament of the
+    /// configured tournament contract.
+    Tournament(u64),
+}
+
+/// Hash function used to commit to a badge's criteria document.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HashAlgo {
+    Sha256 = 0,
+    Keccak256 = 1,
+}
+
+/// How much event detail the contract emits. Awards, definitions,
+/// revocations, and reward movements are emitted at every level.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EventLevel {
+    /// Only the events above.
+    Minimal = 0,
+    /// Adds offer and privacy events (default).
+    Standard = 1,
+    /// Adds `UserEvaluated` and every `StorageBumped`.
+    Verbose = 2,
+}
+
+/// Events emitted by batch operations.
+///
+/// `PerItem` keeps full per-award detail at the cost of one event per user;
+/// `Summary` emits a single `BatchAwarded` per call, which indexers must
+/// reconcile against storage (e.g. `badges_of`) for per-user detail.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BatchEventMode {
+    /// One `BadgeAwarded` per user (default).
+    PerItem = 0,
+    /// One `BatchAwarded` per call.
+    Summary = 1,
+    /// Both of the above.
+    Both = 2,
+}
+
+/// How badge rewards are paid.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SettlementMode {
+    /// Awards credit a claimable balance paid by `claim_rewards` (default).
+    EventDriven = 0,
+    /// Awards transfer the reward to the user immediately.
+    Atomic = 1,
+}
+
+/// When a badge may be awarded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AwardWindow {
+    /// No time restriction.
+    Always,
+    /// Only between the `(start, end)` ledgers, inclusive.
+    Between(u32, u32),
+}
+
+impl BadgeDefinition {
+    /// A definition with the given commitment and reward and every optional
+    /// policy disabled. This is what `define_badge` stores.
+    pub fn new(criteria_hash: BytesN<32>, reward: i128) -> Self {
+        BadgeDefinition {
+            criteria_hash,
+            hash_algo: HashAlgo::Sha256,
+            reward,
+            require_evaluation: false,
+            award_window: AwardWindow::Always,
+            badge_family: 0,
+            cooldown_ledgers: 0,
+            max_supply: None,
+            verifier: None,
+            name: None,
+            reward_token: None,
+            scope: BadgeScope::Global,
+            stackable: false,
+        }
+    }
+}
+
+/// Schema version 1 layout of `BadgeDefinition`, read only by `migrate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeDefinitionV1 {
+    pub criteria_hash: BytesN<32>,
+    pub reward: i128,
+}
+
+/// Schema version 2 layout of `BadgeDefinition`, read only by `migrate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeDefinitionV2 {
+    pub criteria_hash: BytesN<32>,
+    pub hash_algo: HashAlgo,
+    pub reward: i128,
+    pub require_evaluation: bool,
+    pub award_window: AwardWindow,
+    pub badge_family: u32,
+    pub cooldown_ledgers: u32,
+    pub max_supply: Option<u32>,
+    pub verifier: Option<Address>,
+}
+
+/// Schema version 3 layout of `BadgeDefinition`, read only by `migrate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeDefinitionV3 {
+    pub criteria_hash: BytesN<32>,
+    pub hash_algo: HashAlgo,
+    pub reward: i128,
+    pub require_evaluation: bool,
+    pub award_window: AwardWindow,
+    pub badge_family: u32,
+    pub cooldown_ledgers: u32,
+    pub max_supply: Option<u32>,
+    pub verifier: Option<Address>,
+    pub name: Option<String>,
+}
+
+/// Schema version 4 layout of `BadgeDefinition`, read only by `migrate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeDefinitionV4 {
+    pub criteria_hash: BytesN<32>,
+    pub hash_algo: HashAlgo,
+    pub reward: i128,
+    pub require_evaluation: bool,
+    pub award_window: AwardWindow,
+    pub badge_family: u32,
+    pub cooldown_ledgers: u32,
+    pub max_supply: Option<u32>,
+    pub verifier: Option<Address>,
+    pub name: Option<String>,
+    pub reward_token: Option<Address>,
+}
+
+/// Schema version 5 layout of `BadgeDefinition`, read only by `migrate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BadgeDefinitionV5 {
+    pub criteria_hash: BytesN<32>,
+    pub hash_algo: HashAlgo,
+    pub reward: i128,
+    pub require_evaluation: bool,
+    pub award_window: AwardWindow,
+    pub badge_family: u32,
+    pub cooldown_ledgers: u32,
+    pub max_supply: Option<u32>,
+    pub verifier: Option<Address>,
+    pub name: Option<String>,
+    pub reward_token: Option<Address>,
+    pub scope: BadgeScope,
 }
 
 /// Human-readable metadata attached to a badge.
@@ -122,6 +476,82 @@ pub struct BadgeSummary {
     pub award_rules: String,
 }
 
+/// Persistent entry counts returned by `storage_stats`, read from global
+/// counters maintained on define, delete, award, and revoke rather than by
+/// scanning keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StorageStats {
+    /// Defined badges, each with a `Badge` entry and an index slot.
+    pub badges: u32,
+    /// Badges that are meta badges, each with a `MetaBadge` entry.
+    pub meta_badges: u32,
+    /// Current holdings across all users. Each has an `AwardRecord` and
+    /// `AwardOrdinalOf` entry and a slot in its holder's `UserBadges` list.
+    pub holdings: u64,
+    /// Awards ever made, including since-revoked ones.
+    pub awards_issued: u64,
+}
+
+/// Why a badge was awarded. Emitted as a `BadgeAwarded` topic so indexers can
+/// filter awards by provenance; nothing about the reason is stored on-chain.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AwardReason {
+    /// Awarded by hand by the admin.
+    Manual = 0,
+    /// Awarded by an automated pipeline (e.g. a game server hook).
+    Automated = 1,
+    /// Re-issued while migrating holdings from another system.
+    Migration = 2,
+}
+
+/// Details of one badge held by a user, as returned by `badges_of_detailed`.
+///
+/// `note_hash` commits to an off-chain citation for the award (e.g. "won
+/// tournament #42"); all zeroes when none was given. Badges awarded before
+/// award records existed report `awarded_ledger` 0 and reason `Manual`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AwardRecord {
+    pub badge_id: u64,
+    pub awarded_ledger: u32,
+    pub reason: AwardReason,
+    pub note_hash: BytesN<32>,
+}
+
+/// A badge reward vesting linearly from `start_ledger` over
+/// `duration_ledgers`, with nothing claimable before the cliff.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingEntry {
+    pub total: i128,
+    pub claimed: i128,
+    pub start_ledger: u32,
+    pub cliff_ledgers: u32,
+    pub duration_ledgers: u32,
+    /// The badge's `reward_token` override at award time. `None` = the
+    /// contract's `RewardToken`.
+    pub token: Option<Address>,
+}
+
+impl VestingEntry {
+    /// Amount vested at `ledger`, claimed or not.
+    pub fn vested_at(&self, ledger: u32) -> i128 {
+        let elapsed = ledger.saturating_sub(self.start_ledger);
+        if elapsed < self.cliff_ledgers {
+            0
+        } else if elapsed >= self.duration_ledgers {
+            self.total
+        } else {
+            // Split to avoid overflowing `total * elapsed`.
+            let (elapsed, duration) = (elapsed as i128, self.duration_ledgers as i128);
+            self.total / duration * elapsed + self.total % duration * elapsed / duration
+        }
+    }
+}
+
 /// Per-user claim-status snapshot for a single badge.
 ///
 /// `badge_found` is `false` when the badge_id is not defined.
@@ -135,6 +565,53 @@ pub struct ClaimStatusSnapshot {
     pub badge_found: bool,
 }
 
+/// Whether a user holds a badge, as reported by `holds_badge_status`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HoldStatus {
+    /// The user holds the badge.
+    Holds = 0,
+    /// The user has a holdings record that does not include the badge.
+    DoesNotHold = 1,
+    /// The user has no holdings record at all.
+    NoRecord = 2,
+}
+
+// ---------------------------------------------------------------------------
+// External interfaces
+// ---------------------------------------------------------------------------
+
+/// Interface a badge `verifier` contract must implement.
+#[contractclient(name = "CriteriaVerifierClient")]
+pub trait CriteriaVerifier {
+    /// Return whether `user` meets the badge criteria given `proof`.
+    fn verify(env: Env, user: Address, proof: Bytes) -> bool;
+}
+
+/// Participation query the tournament contract must answer for
+/// tournament-scoped badges.
+#[contractclient(name = "TournamentViewClient")]
+pub trait TournamentView {
+    /// Return whether `player` joined tournament `id`.
+    fn is_joined(env: Env, id: u64, player: Address) -> bool;
+}
+
+/// Version query the payout token must answer before atomic payouts when
+/// `expected_reward_abi` is set.
+#[contractclient(name = "RewardAbiClient")]
+pub trait RewardAbi {
+    /// Return the interface version the contract implements.
+    fn abi_version(env: Env) -> u32;
+}
+
+/// Callback a badge `subscriber` contract must implement.
+#[contractclient(name = "BadgeSubscriberClient")]
+pub trait BadgeSubscriber {
+    /// Called after `badge_id` is awarded to `user`.
+    fn on_badge_awarded(env: Env, user: Address, badge_id: u64);
+}
+
 // ---------------------------------------------------------------------------
 // Events
 // ---------------------------------------------------------------------------
@@ -145,6 +622,24 @@ pub struct BadgeDefined {
     pub badge_id: u64,
     pub criteria_hash: BytesN<32>,
     pub reward: i128,
+    pub reward_token_decimals: u32,
+    pub name: Option<String>,
+}
+
+#[contractevent]
+pub struct AdminRenounced {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct Paused {
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct Unpaused {
+    pub admin: Address,
 }
 
 #[contractevent]
@@ -161,7 +656,172 @@ pub struct BadgeAwarded {
     pub user: Address,
     #[topic]
     pub badge_id: u64,
+    #[topic]
+    pub reason: AwardReason,
+    /// Token `reward` is paid in: the badge override, else the default
+    /// `RewardToken`. `NO_REWARD_TOKEN` when the badge carries no reward or
+    /// no token is configured, so indexers can always filter on it.
+    #[topic]
+    pub reward_token: Address,
     pub reward: i128,
+    pub reward_token_decimals: u32,
+    pub note_hash: BytesN<32>,
+}
+
+/// Emitted alongside `BadgeAwarded` for every award with a non-zero reward,
+/// including batch awards in `Summary` mode, so payout services can follow
+/// one stream. `token` is resolved as in `BadgeAwarded::reward_token`.
+#[contractevent]
+pub struct RewardDue {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+    pub amount: i128,
+    pub token: Option<Address>,
+}
+
+/// Summary of an `award_badge_batch` call. `first_user` and `last_user` are
+/// the ends of the submitted list, in order.
+#[contractevent]
+pub struct BatchAwarded {
+    #[topic]
+    pub badge_id: u64,
+    pub count: u32,
+    pub first_user: Address,
+    pub last_user: Address,
+}
+
+#[contractevent]
+pub struct SettlementModeSet {
+    pub mode: SettlementMode,
+}
+
+#[contractevent]
+pub struct RewardClaimed {
+    #[topic]
+    pub user: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Emitted on every persistent TTL bump while verbose storage events are
+/// enabled. `key_kind` identifies the `DataKey` variant (see `key_kind`).
+#[contractevent]
+pub struct StorageBumped {
+    pub key_kind: u32,
+    pub new_ttl: u32,
+}
+
+#[contractevent]
+pub struct VerifierSet {
+    #[topic]
+    pub badge_id: u64,
+    pub verifier: Option<Address>,
+}
+
+#[contractevent]
+pub struct BadgeOffered {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+}
+
+#[contractevent]
+pub struct PrivacySet {
+    #[topic]
+    pub user: Address,
+    pub private: bool,
+}
+
+#[contractevent]
+pub struct EpochAdvanced {
+    pub epoch: u32,
+}
+
+#[contractevent]
+pub struct BadgeDefinitionDeleted {
+    #[topic]
+    pub badge_id: u64,
+}
+
+#[contractevent]
+pub struct HolderCountRepaired {
+    #[topic]
+    pub badge_id: u64,
+    pub previous: u32,
+    pub holders: u32,
+}
+
+#[contractevent]
+pub struct SubscriberNotified {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+    pub subscriber: Address,
+    pub success: bool,
+}
+
+#[contractevent]
+pub struct BadgeRevoked {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+}
+
+#[contractevent]
+pub struct BadgeAccepted {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+}
+
+#[contractevent]
+pub struct BadgeDeclined {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+}
+
+#[contractevent]
+pub struct RewardsFunded {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct RewardEscrowed {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct RewardReleased {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct RewardForfeited {
+    #[topic]
+    pub user: Address,
+    #[topic]
+    pub badge_id: u64,
+    pub amount: i128,
 }
 
 // ---------------------------------------------------------------------------
@@ -183,738 +843,6996 @@ impl AchievementBadge {
     /// and award badges. `reward_contract` is the address of the downstream
     /// contract that handles token payouts (e.g., PrizePool). It is stored for
     /// future integration but is not called directly in this contract.
-    pub fn init(env: Env, admin: Address, reward_contract: Address) -> Result<(), Error> {
-        if env.storage().instance().has(&DataKey::Admin) {
+    /// `expected_reward_abi`, if nonzero, is the `abi_version` every token an
+    /// `Atomic` award transfers must report.
+    /// `reward_token_decimals` (at most 18) lets indexers render raw `reward`
+    /// amounts; it does not affect any arithmetic. `name` (1 to
+    /// `MAX_CONTRACT_NAME_LEN` bytes) labels this instance and never changes.
+    pub fn init(
+        env: Env,
+        admin: Address,
+        reward_contract: Address,
+        reward_token_decimals: u32,
+        name: String,
+        expected_reward_abi: u32,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) || is_admin_renounced(&env) {
             return Err(Error::AlreadyInitialized);
         }
+        if reward_token_decimals > MAX_REWARD_TOKEN_DECIMALS
+            || name.is_empty()
+            || name.len() > MAX_CONTRACT_NAME_LEN
+        {
+            return Err(Error::InvalidInput);
+        }
 
-        admin.require_auth();
+        require_auth(&admin);
 
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
             .set(&DataKey::RewardContract, &reward_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardTokenDecimals, &reward_token_decimals);
+        env.storage()
+            .instance()
+            .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        env.storage().instance().set(&DataKey::ContractName, &name);
+        env.storage()
+            .instance()
+            .set(&DataKey::ExpectedRewardAbi, &expected_reward_abi);
 
         Ok(())
     }
 
+    /// Return the payout token interface version required for atomic
+    /// payouts, or 0 if it is not checked.
+    pub fn reward_abi(env: Env) -> u32 {
+        expected_reward_abi(&env)
+    }
+
+    /// Whether `init` has been called. Stays `true` after `renounce_admin`.
+    pub fn is_initialized(env: Env) -> bool {
+        require_initialized(&env).is_ok()
+    }
+
+    /// Return the name given at `init`, or an empty string for deployments
+    /// initialized without one.
+    pub fn contract_name(env: Env) -> String {
+        env.storage()
+            .instance()
+            .get(&DataKey::ContractName)
+            .unwrap_or_else(|| String::from_str(&env, ""))
+    }
+
     // -----------------------------------------------------------------------
-    // define_badge
+    // migrate
     // -----------------------------------------------------------------------
 
-    /// Define a new achievement badge. Admin only.
+    /// Rewrite badge definitions stored under an older layout into the
+    /// current one. Admin only.
     ///
-    /// `badge_id` must be unique; re-defining an existing badge returns
-    /// `BadgeAlreadyExists`. `criteria_hash` is the 32-byte SHA-256 hash of
-    /// the off-chain criteria document. `reward` is the token amount awarded
-    /// through the reward contract on badge issuance; use 0 for no reward.
-    pub fn define_badge(
+    /// `from_version` must match the stored version (`SchemaVersionMismatch`
+    /// otherwise). Older layouts cannot be enumerated, so the calls together
+    /// must list every badge defined before the upgrade in `badge_ids`; large
+    /// sets can be split across batches. Upgraded definitions get every newer
+    /// field disabled, and version 1 definitions are added to the badge and
+    /// criteria-hash indexes and to the `storage_stats` counters; entries
+    /// already in the current layout are skipped. Pass `finish` on the last
+    /// batch to record `SCHEMA_VERSION`, which also resets the badge counter
+    /// to the length of the badge index.
+    ///
+    /// Holders cannot be enumerated either, so holder counts of awards made
+    /// before the upgrade are not seeded. Run `recompute_holders` for every
+    /// such badge before `finish`; until then `max_supply` and
+    /// `delete_badge_definition` do not see those holders. Returns the number
+    /// of definitions rewritten.
+    pub fn migrate(
         env: Env,
         admin: Address,
-        badge_id: u64,
-        criteria_hash: BytesN<32>,
-        reward: i128,
-    ) -> Result<(), Error> {
+        from_version: u32,
+        badge_ids: Vec<u64>,
+        finish: bool,
+    ) -> Result<u32, Error> {
         require_initialized(&env)?;
         require_admin(&env, &admin)?;
 
-        if reward < 0 {
-            return Err(Error::InvalidInput);
+        if from_version != schema_version(&env) {
+            return Err(Error::SchemaVersionMismatch);
         }
 
-        let key = DataKey::Badge(badge_id);
-        if env.storage().persistent().has(&key) {
-            return Err(Error::BadgeAlreadyExists);
+        let mut migrated = 0u32;
+        for badge_id in badge_ids.iter() {
+            let key = DataKey::Badge(badge_id);
+            let Some(raw) = env.storage().persistent().get::<_, Map<Symbol, Val>>(&key) else {
+                continue;
+            };
+            // Each version adds a field: `hash_algo` in 2, `name` in 3,
+            // `reward_token` in 4, `scope` in 5, `stackable` in 6.
+            if raw.contains_key(Symbol::new(&env, "stackable")) {
+                continue;
+            }
+            let is_v5 = raw.contains_key(Symbol::new(&env, "scope"));
+            let is_v4 = raw.contains_key(Symbol::new(&env, "reward_token"));
+            let is_v3 = raw.contains_key(Symbol::new(&env, "name"));
+            let is_v2 = raw.contains_key(Symbol::new(&env, "hash_algo"));
+            let raw: Val = raw.into_val(&env);
+            let definition = if is_v5 {
+                let old = BadgeDefinitionV5::try_from_val(&env, &raw)
+                    .map_err(|_| Error::SchemaVersionMismatch)?;
+                BadgeDefinition {
+                    criteria_hash: old.criteria_hash,
+                    hash_algo: old.hash_algo,
+                    reward: old.reward,
+                    require_evaluation: old.require_evaluation,
+                    award_window: old.award_window,
+                    badge_family: old.badge_family,
+                    cooldown_ledgers: old.cooldown_ledgers,
+                    max_supply: old.max_supply,
+                    verifier: old.verifier,
+                    name: old.name,
+                    reward_token: old.reward_token,
+                    scope: old.scope,
+                    stackable: false,
+                }
+            } else if is_v4 {
+                let old = BadgeDefinitionV4::try_from_val(&env, &raw)
+                    .map_err(|_| Error::SchemaVersionMismatch)?;
+                BadgeDefinition {
+                    criteria_hash: old.criteria_hash,
+                    hash_algo: old.hash_algo,
+                    reward: old.reward,
+                    require_evaluation: old.require_evaluation,
+                    award_window: old.award_window,
+                    badge_family: old.badge_family,
+                    cooldown_ledgers: old.cooldown_ledgers,
+                    max_supply: old.max_supply,
+                    verifier: old.verifier,
+                    name: old.name,
+                    reward_token: old.reward_token,
+                    scope: BadgeScope::Global,
+                    stackable: false,
+                }
+            } else if is_v3 {
+                let old = BadgeDefinitionV3::try_from_val(&env, &raw)
+                    .map_err(|_| Error::SchemaVersionMismatch)?;
+                BadgeDefinition {
+                    criteria_hash: old.criteria_hash,
+                    hash_algo: old.hash_algo,
+                    reward: old.reward,
+                    require_evaluation: old.require_evaluation,
+                    award_window: old.award_window,
+                    badge_family: old.badge_family,
+                    cooldown_ledgers: old.cooldown_ledgers,
+                    max_supply: old.max_supply,
+                    verifier: old.verifier,
+                    name: old.name,
+                    reward_token: None,
+                    scope: BadgeScope::Global,
+                    stackable: false,
+                }
+            } else if is_v2 {
+                let old = BadgeDefinitionV2::try_from_val(&env, &raw)
+                    .map_err(|_| Error::SchemaVersionMismatch)?;
+                BadgeDefinition {
+                    criteria_hash: old.criteria_hash,
+                    hash_algo: old.hash_algo,
+                    reward: old.reward,
+                    require_evaluation: old.require_evaluation,
+                    award_window: old.award_window,
+                    badge_family: old.badge_family,
+                    cooldown_ledgers: old.cooldown_ledgers,
+                    max_supply: old.max_supply,
+                    verifier: old.verifier,
+                    name: None,
+                    reward_token: None,
+                    scope: BadgeScope::Global,
+                    stackable: false,
+                }
+            } else {
+                let old = BadgeDefinitionV1::try_from_val(&env, &raw)
+                    .map_err(|_| Error::SchemaVersionMismatch)?;
+                BadgeDefinition::new(old.criteria_hash, old.reward)
+            };
+
+            env.storage().persistent().set(&key, &definition);
+            bump(&env, &key);
+            // Version 1 kept no indexes or counters.
+            if !is_v2 {
+                index_badge_hash(&env, &definition.criteria_hash, badge_id);
+                index_badge(&env, badge_id);
+                add_stat(&env, DataKey::BadgeCount, 1);
+                if env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::MetaBadge(badge_id))
+                {
+                    add_stat(&env, DataKey::MetaBadgeCount, 1);
+                }
+            }
+            migrated += 1;
         }
 
-        let definition = BadgeDefinition {
-            criteria_hash: criteria_hash.clone(),
-            reward,
-        };
-        env.storage().persistent().set(&key, &definition);
+        if finish {
+            let indexed: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::BadgeIndex)
+                .unwrap_or_else(|| vec![&env]);
+            env.storage()
+                .instance()
+                .set(&DataKey::BadgeCount, &u64::from(indexed.len()));
+            env.storage()
+                .instance()
+                .set(&DataKey::SchemaVersion, &SCHEMA_VERSION);
+        }
+
+        Ok(migrated)
+    }
+
+    /// Return the stored storage layout version.
+    pub fn schema_version(env: Env) -> u32 {
+        schema_version(&env)
+    }
+
+    /// Return the reward token's decimals as configured at `init`.
+    pub fn reward_token_decimals(env: Env) -> u32 {
+        reward_token_decimals(&env)
+    }
+
+    // -----------------------------------------------------------------------
+    // renounce_admin
+    // -----------------------------------------------------------------------
+
+    /// Permanently burn the admin role. Admin only. Irreversible.
+    ///
+    /// Deletes the stored admin and sets `AdminRenounced`. Afterwards every
+    /// admin-gated method returns `AdminRenounced` and the contract cannot be
+    /// re-initialized; read methods keep working.
+    pub fn renounce_admin(env: Env, admin: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        // Nobody could lift the pause afterwards.
+        require_not_paused(&env)?;
+
+        env.storage().instance().remove(&DataKey::Admin);
         env.storage()
-            .persistent()
-            .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+            .instance()
+            .set(&DataKey::AdminRenounced, &true);
 
-        BadgeDefined {
-            badge_id,
-            criteria_hash,
-            reward,
-        }
-        .publish(&env);
+        AdminRenounced { admin }.publish(&env);
 
         Ok(())
     }
 
     // -----------------------------------------------------------------------
-    // evaluate_user
+    // define_badge
     // -----------------------------------------------------------------------
 
-    /// Signal that a user has been evaluated against a badge's criteria.
+    /// Define a new achievement badge. Admin only.
+    ///
+    /// `badge_id` must be unique; re-defining an existing badge returns
+    /// `BadgeAlreadyExists`. `criteria_hash` is the 32-byte SHA-256 hash of
+    /// the off-chain criteria document. `reward` is the token amount awarded
+    /// through the reward contract on badge issuance; use 0 for no reward.
+    ///
+    /// All optional award policies are disabled; use `define_badge_full` to
+    /// configure them.
+    pub fn define_badge(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        criteria_hash: BytesN<32>,
+        reward: i128,
+    ) -> Result<(), Error> {
+        Self::define_badge_full(
+            env,
+            admin,
+            badge_id,
+            BadgeDefinition::new(criteria_hash, reward),
+        )
+    }
+
+    /// Define a new achievement badge from a complete `BadgeDefinition`,
+    /// including its optional award policies. Admin only.
+    ///
+    /// Subject to the same uniqueness and `reward >= 0` rules as
+    /// `define_badge`. An `award_window` must satisfy `start < end`.
+    pub fn define_badge_full(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        definition: BadgeDefinition,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        define(&env, badge_id, definition)
+    }
+
+    /// Delete a badge definition nobody holds so its ID can be redefined.
     /// Admin only.
     ///
-    /// This is an administrative action that emits an auditable event. It does
-    /// not award the badge; call `award_badge` separately if the evaluation
-    /// determines the user qualifies. The badge must exist.
-    pub fn evaluate_user(env: Env, admin: Address, user: Address, badge_id: u64) -> Result<(), Error> {
+    /// Returns `BadgeNotFound` for an undefined badge and `BadgeLocked` while
+    /// it has holders or pending offers. Removes the definition with its
+    /// metadata, prerequisites, claim root, vesting schedule, subscriber,
+    /// 64-byte hash, and index entries.
+    pub fn delete_badge_definition(env: Env, admin: Address, badge_id: u64) -> Result<(), Error> {
         require_initialized(&env)?;
         require_admin(&env, &admin)?;
 
-        // Badge must exist before an evaluation can be recorded.
-        require_badge_exists(&env, badge_id)?;
+        let badge = require_badge_exists(&env, badge_id)?;
+        if holder_count(&env, badge_id) > 0 || offer_count(&env, badge_id) > 0 {
+            return Err(Error::BadgeLocked);
+        }
 
-        UserEvaluated {
-            user,
-            badge_id,
+        let storage = env.storage().persistent();
+        if storage.has(&DataKey::MetaBadge(badge_id)) {
+            add_stat(&env, DataKey::MetaBadgeCount, -1);
         }
-        .publish(&env);
+        add_stat(&env, DataKey::BadgeCount, -1);
+        for key in [
+            DataKey::Badge(badge_id),
+            DataKey::BadgeMeta(badge_id),
+            DataKey::MetaBadge(badge_id),
+            DataKey::ClaimRoot(badge_id),
+            DataKey::HolderCount(badge_id),
+            DataKey::Badge64(badge_id),
+            DataKey::BadgeVesting(badge_id),
+            DataKey::BadgeSubscriber(badge_id),
+            DataKey::OfferCount(badge_id),
+        ] {
+            storage.remove(&key);
+        }
+        unindex(&env, DataKey::HashToBadges(badge.criteria_hash), badge_id);
+        unindex(&env, DataKey::BadgeIndex, badge_id);
+
+        BadgeDefinitionDeleted { badge_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the badge's name, or `None` if it is unnamed or undefined.
+    pub fn badge_name(env: Env, badge_id: u64) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get::<_, BadgeDefinition>(&DataKey::Badge(badge_id))
+            .and_then(|badge| badge.name)
+    }
+
+    /// Define a badge committed to by a 64-byte hash (e.g. SHA-512) of its
+    /// criteria document. Admin only.
+    ///
+    /// The full hash is stored under `Badge64(badge_id)`; the definition's
+    /// 32-byte `criteria_hash` is the SHA-256 of it, so indexes and award
+    /// paths work unchanged. A badge_id uses exactly one of the two variants.
+    pub fn define_badge_64(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        criteria_hash: BytesN<64>,
+        reward: i128,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        let digest: BytesN<32> = env.crypto().sha256(&criteria_hash.clone().into()).into();
+        define(&env, badge_id, BadgeDefinition::new(digest, reward))?;
+
+        let key = DataKey::Badge64(badge_id);
+        env.storage().persistent().set(&key, &criteria_hash);
+        bump(&env, &key);
 
         Ok(())
     }
 
+    /// Return the 64-byte criteria hash of a badge defined by
+    /// `define_badge_64`, or `None` for 32-byte badges.
+    pub fn criteria_hash_64(env: Env, badge_id: u64) -> Option<BytesN<64>> {
+        env.storage().persistent().get(&DataKey::Badge64(badge_id))
+    }
+
     // -----------------------------------------------------------------------
-    // award_badge
+    // define_badge_with_claim_root / claim_badge
     // -----------------------------------------------------------------------
 
-    /// Award `badge_id` to `user`. Admin only.
-    ///
-    /// The badge must be defined. Each badge can only be awarded once per user;
-    /// duplicate awards return `BadgeAlreadyAwarded`. The badge is appended to
-    /// the user's persistent badge list, which is created on first award.
+    /// Define a badge whose eligible holders are committed to by a merkle
+    /// root, for airdrops too large for per-user admin writes. Admin only.
     ///
-    /// If `badge.reward > 0`, a `BadgeAwarded` event is emitted with the
-    /// reward amount so off-chain services can trigger the downstream payout
-    /// via the reward contract.
-    pub fn award_badge(env: Env, admin: Address, user: Address, badge_id: u64) -> Result<(), Error> {
+    /// Leaves are `sha256(user.to_xdr())`; interior nodes hash the two
+    /// children in ascending byte order, so proofs need no left/right flags.
+    /// The badge is otherwise a regular definition.
+    pub fn define_badge_with_claim_root(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        criteria_hash: BytesN<32>,
+        reward: i128,
+        claim_root: BytesN<32>,
+    ) -> Result<(), Error> {
         require_initialized(&env)?;
         require_admin(&env, &admin)?;
 
-        let badge = require_badge_exists(&env, badge_id)?;
+        define(&env, badge_id, BadgeDefinition::new(criteria_hash, reward))?;
+
+        let key = DataKey::ClaimRoot(badge_id);
+        env.storage().persistent().set(&key, &claim_root);
+        bump(&env, &key);
+
+        Ok(())
+    }
+
+    /// Claim a merkle-root badge by proving `user` is in the eligible set.
+    /// User must authorize.
+    ///
+    /// Returns `BadgeNotFound` if `badge_id` has no claim root and
+    /// `InvalidProof` if `proof` does not lead from the user's leaf to the
+    /// stored root. All regular award checks still apply.
+    pub fn claim_badge(
+        env: Env,
+        user: Address,
+        badge_id: u64,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_auth(&user);
 
-        // Guard against duplicate awards.
-        let user_key = DataKey::UserBadges(user.clone());
-        let mut badges: Vec<u64> = env
+        let root: BytesN<32> = env
             .storage()
             .persistent()
-            .get(&user_key)
-            .unwrap_or_else(|| vec![&env]);
+            .get(&DataKey::ClaimRoot(badge_id))
+            .ok_or(Error::BadgeNotFound)?;
+
+        let mut node: BytesN<32> = env.crypto().sha256(&user.clone().to_xdr(&env)).into();
+        for sibling in proof.iter() {
+            node = hash_pair(&env, &node, &sibling);
+        }
+        if node != root {
+            return Err(Error::InvalidProof);
+        }
+
+        award(&env, &user, badge_id, AwardReason::Automated, no_note(&env))
+    }
+
+    // -----------------------------------------------------------------------
+    // define_meta_badge / claim_meta
+    // -----------------------------------------------------------------------
+
+    /// Define a meta badge earned by holding every badge in `required`.
+    /// Admin only.
+    ///
+    /// The meta badge is a regular badge (same uniqueness and reward rules)
+    /// whose `criteria_hash` is the SHA-256 of the big-endian prerequisite
+    /// IDs, so the prerequisite set itself is the committed criteria.
+    /// `required` must be non-empty, must not contain `badge_id`, and every
+    /// entry must already be defined.
+    pub fn define_meta_badge(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        required: Vec<u64>,
+        reward: i128,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
 
-        for i in 0..badges.len() {
-            if badges.get(i).unwrap() == badge_id {
-                return Err(Error::BadgeAlreadyAwarded);
+        if required.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        let mut preimage = Bytes::new(&env);
+        for id in required.iter() {
+            if id == badge_id {
+                return Err(Error::InvalidInput);
             }
+            require_badge_exists(&env, id)?;
+            preimage.extend_from_array(&id.to_be_bytes());
         }
 
-        badges.push_back(badge_id);
-        env.storage().persistent().set(&user_key, &badges);
-        env.storage()
+        let criteria_hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        define(&env, badge_id, BadgeDefinition::new(criteria_hash, reward))?;
+
+        let key = DataKey::MetaBadge(badge_id);
+        env.storage().persistent().set(&key, &required);
+        bump(&env, &key);
+        add_stat(&env, DataKey::MetaBadgeCount, 1);
+
+        Ok(())
+    }
+
+    /// Award meta badge `badge_id` to `user` if they hold every prerequisite.
+    ///
+    /// Permissionless: anyone (typically the user) may trigger the claim,
+    /// since it can only succeed when the prerequisites are already met.
+    /// Returns `BadgeNotFound` if `badge_id` is not a meta badge and
+    /// `PrerequisitesNotMet` if any prerequisite is missing. All regular
+    /// award checks, including the duplicate guard, still apply.
+    pub fn claim_meta(env: Env, user: Address, badge_id: u64) -> Result<(), Error> {
+        require_initialized(&env)?;
+
+        let required: Vec<u64> = env
+            .storage()
             .persistent()
-            .extend_ttl(&user_key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+            .get(&DataKey::MetaBadge(badge_id))
+            .ok_or(Error::BadgeNotFound)?;
 
-        BadgeAwarded {
-            user,
-            badge_id,
-            reward: badge.reward,
+        if !holds_all(&env, &user, &required) {
+            return Err(Error::PrerequisitesNotMet);
         }
-        .publish(&env);
 
-        Ok(())
+        award(&env, &user, badge_id, AwardReason::Automated, no_note(&env))
     }
 
-    // -----------------------------------------------------------------------
-    // badges_of
-    // -----------------------------------------------------------------------
+    /// Return the `users` who currently hold every prerequisite of meta
+    /// badge `meta_badge_id`, in input order, using the same check as
+    /// `claim_meta`. Only the first `MAX_PAGE_SIZE` users are considered.
+    /// Empty if `meta_badge_id` is not a meta badge.
+    pub fn meta_eligible(env: Env, meta_badge_id: u64, users: Vec<Address>) -> Vec<Address> {
+        let mut eligible = vec![&env];
+        let Some(required) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<u64>>(&DataKey::MetaBadge(meta_badge_id))
+        else {
+            return eligible;
+        };
 
-    /// Return the list of badge IDs awarded to `user`.
-    ///
-    /// Returns an empty list if the user has not been awarded any badges.
-    /// Does not require initialization — a user with no badges trivially has
-    /// an empty list regardless of contract state.
-    pub fn badges_of(env: Env, user: Address) -> Vec<u64> {
-        let user_key = DataKey::UserBadges(user);
+        for user in users.iter().take(MAX_PAGE_SIZE as usize) {
+            if holds_all(&env, &user, &required) {
+                eligible.push_back(user);
+            }
+        }
+        eligible
+    }
+
+    /// Return the prerequisite badge IDs of meta badge `badge_id`, or an
+    /// empty list if it is not a meta badge.
+    pub fn meta_requirements(env: Env, badge_id: u64) -> Vec<u64> {
         env.storage()
             .persistent()
-            .get(&user_key)
+            .get(&DataKey::MetaBadge(badge_id))
             .unwrap_or_else(|| vec![&env])
     }
 
+    /// Return `(held, required)`: how many prerequisites of meta badge
+    /// `badge_id` `user` holds, out of how many. `(0, 0)` if `badge_id` is
+    /// not a meta badge. `held` is 0 for private users; `claim_meta` and
+    /// `meta_eligible` still check the real holdings.
+    pub fn meta_progress(env: Env, user: Address, badge_id: u64) -> (u32, u32) {
+        let required = Self::meta_requirements(env.clone(), badge_id);
+        let held_badges = visible_holdings(&env, &user);
+        let held = required
+            .iter()
+            .filter(|id| held_badges.contains(id))
+            .count() as u32;
+        (held, required.len())
+    }
+
     // -----------------------------------------------------------------------
-    // set_badge_metadata
+    // verify_criteria
     // -----------------------------------------------------------------------
 
-    /// Attach human-readable metadata to an existing badge. Admin only.
+    /// Check whether `criteria` is the document committed to by `badge_id`.
     ///
-    /// The badge must already be defined via `define_badge`. Metadata may be
-    /// updated by calling this again; each write extends the TTL.
+    /// Hashes `criteria` with the badge's `hash_algo` and compares the result
+    /// to the stored `criteria_hash`. Returns `BadgeNotFound` for unknown
+    /// badges.
     ///
-    /// Keeping metadata separate from the immutable `BadgeDefinition` allows
-    /// copy edits and future metadata field additions without touching the
-    /// on-chain criteria commitment.
-    pub fn set_badge_metadata(
+    /// The host has no 64-byte hash function, so for `define_badge_64`
+    /// badges `criteria` must be the 64-byte digest itself, computed
+    /// off-chain.
+    pub fn verify_criteria(env: Env, badge_id: u64, criteria: Bytes) -> Result<bool, Error> {
+        let badge = require_badge_exists(&env, badge_id)?;
+
+        if let Some(hash_64) = env
+            .storage()
+            .persistent()
+            .get::<_, BytesN<64>>(&DataKey::Badge64(badge_id))
+        {
+            return Ok(criteria == Bytes::from(hash_64));
+        }
+
+        let digest: BytesN<32> = match badge.hash_algo {
+            HashAlgo::Sha256 => env.crypto().sha256(&criteria).into(),
+            HashAlgo::Keccak256 => env.crypto().keccak256(&criteria).into(),
+        };
+
+        Ok(digest == badge.criteria_hash)
+    }
+
+    // -----------------------------------------------------------------------
+    // evaluate_user
+    // -----------------------------------------------------------------------
+
+    /// Signal that a user has been evaluated against a badge's criteria.
+    /// Admin only.
+    ///
+    /// This is an administrative action that emits an auditable event and
+    /// records the evaluation ledger. It does not award the badge; call
+    /// `award_badge` separately if the evaluation determines the user
+    /// qualifies. The badge must exist.
+    pub fn evaluate_user(
         env: Env,
         admin: Address,
+        user: Address,
         badge_id: u64,
-        title: String,
-        description: String,
-        award_rules: String,
     ) -> Result<(), Error> {
         require_initialized(&env)?;
         require_admin(&env, &admin)?;
+
+        // Badge must exist before an evaluation can be recorded.
         require_badge_exists(&env, badge_id)?;
 
-        let entry = BadgeMetaEntry { title, description, award_rules };
-        let key = DataKey::BadgeMeta(badge_id);
-        env.storage().persistent().set(&key, &entry);
+        let key = DataKey::Evaluated(user.clone(), badge_id);
         env.storage()
             .persistent()
-            .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+            .set(&key, &env.ledger().sequence());
+        bump(&env, &key);
+
+        if emits(&env, EventLevel::Verbose) {
+            UserEvaluated { user, badge_id }.publish(&env);
+        }
 
         Ok(())
     }
 
+    /// Return whether `user` has ever been evaluated for `badge_id`.
+    pub fn was_evaluated(env: Env, user: Address, badge_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Evaluated(user, badge_id))
+    }
+
+    /// Return the ledger of the most recent evaluation of `user` for
+    /// `badge_id`, or `None` if they were never evaluated.
+    pub fn evaluated_at(env: Env, user: Address, badge_id: u64) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Evaluated(user, badge_id))
+    }
+
     // -----------------------------------------------------------------------
-    // get_badge_summary
+    // award_badge
     // -----------------------------------------------------------------------
 
-    /// Return a combined badge definition + metadata snapshot for `badge_id`.
+    /// Award `badge_id` to `user`. Admin only.
     ///
-    /// This is designed for single-call badge-card rendering — no additional
-    /// reads are required. When `badge_id` is unknown `found` is `false` and
-    /// all other fields carry zero/empty values. Missing metadata fields
-    /// (metadata not yet set) are returned as empty strings.
-    pub fn get_badge_summary(env: Env, badge_id: u64) -> BadgeSummary {
-        let definition: Option<BadgeDefinition> =
-            env.storage().persistent().get(&DataKey::Badge(badge_id));
+    /// The badge must be defined. Each badge can only be awarded once per user;
+    /// duplicate awards return `BadgeAlreadyAwarded`. Badges defined with
+    /// `require_evaluation` return `NotEvaluated` unless `evaluate_user` was
+    /// called for the pair first. Outside the badge's `award_window` the award
+    /// fails with `AwardWindowClosed`. Users already holding
+    /// `max_badges_per_user` badges are rejected with `UserBadgeLimit`. The
+    /// badge is appended to the user's persistent badge list, which is created
+    /// on first award.
+    ///
+    /// If `badge.reward > 0`, a `BadgeAwarded` event is emitted with the
+    /// reward amount so off-chain services can trigger the downstream payout
+    /// via the reward contract.
+    ///
+    /// Equivalent to `award_badge_with_reason` with `AwardReason::Manual`.
+    pub fn award_badge(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+    ) -> Result<(), Error> {
+        Self::award_badge_with_reason(env, admin, user, badge_id, AwardReason::Manual)
+    }
 
-        match definition {
-            None => BadgeSummary {
-                found: false,
-                badge_id,
-                criteria_hash: BytesN::from_array(&env, &[0u8; 32]),
-                reward: 0,
-                title: String::from_str(&env, ""),
-                description: String::from_str(&env, ""),
-                award_rules: String::from_str(&env, ""),
-            },
-            Some(def) => {
-                let meta: Option<BadgeMetaEntry> =
-                    env.storage().persistent().get(&DataKey::BadgeMeta(badge_id));
-                let (title, description, award_rules) = match meta {
-                    Some(m) => (m.title, m.description, m.award_rules),
-                    None => (
-                        String::from_str(&env, ""),
-                        String::from_str(&env, ""),
-                        String::from_str(&env, ""),
-                    ),
-                };
-                BadgeSummary {
-                    found: true,
-                    badge_id,
-                    criteria_hash: def.criteria_hash,
-                    reward: def.reward,
-                    title,
-                    description,
-                    award_rules,
+    /// Award `badge_id` to `user` and tag the award with `reason`. Admin only.
+    ///
+    /// Behaves exactly like `award_badge`; `reason` is surfaced as a
+    /// `BadgeAwarded` topic and kept in the user's award record.
+    pub fn award_badge_with_reason(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+        reason: AwardReason,
+    ) -> Result<(), Error> {
+        let note_hash = no_note(&env);
+        Self::award_badge_with_note(env, admin, user, badge_id, reason, note_hash)
+    }
+
+    /// Award `badge_id` to `user` with a citation. Admin only.
+    ///
+    /// `note_hash` commits to an off-chain document justifying the award; it
+    /// is stored in the award record and emitted in `BadgeAwarded`. Otherwise
+    /// behaves exactly like `award_badge_with_reason`.
+    pub fn award_badge_with_note(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+        reason: AwardReason,
+        note_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        award(&env, &user, badge_id, reason, note_hash)?;
+        record_issuer_award(&env, &admin);
+
+        Ok(())
+    }
+
+    /// Award `badge_id` to `user` on behalf of tournament `source_id`, with
+    /// `AwardReason::Automated`. Admin only; used by the tournament
+    /// contract's auto-award paths.
+    ///
+    /// Behaves like `award_badge`, and additionally records `source_id` for
+    /// `award_source`. A `source_id` of 0 records nothing.
+    pub fn award_badge_with_source(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+        source_id: u64,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        award(&env, &user, badge_id, AwardReason::Automated, no_note(&env))?;
+        record_issuer_award(&env, &admin);
+
+        if source_id != 0 {
+            let key = DataKey::AwardSource(user, badge_id);
+            env.storage().persistent().set(&key, &source_id);
+            bump(&env, &key);
+        }
+
+        Ok(())
+    }
+
+    /// Return the tournament `user` earned `badge_id` in, or 0 for manual
+    /// awards, badges the user does not hold, and private users.
+    pub fn award_source(env: Env, user: Address, badge_id: u64) -> u64 {
+        if is_private(&env, &user) {
+            return 0;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::AwardSource(user, badge_id))
+            .unwrap_or(0)
+    }
+
+    /// Award `badge_id` to `user` on behalf of season `epoch`. Admin only.
+    ///
+    /// Returns `EpochClosed` for an epoch already closed by `advance_epoch`
+    /// and `InvalidInput` for one not yet started, so awards cannot be
+    /// back-dated into a finished season. Otherwise behaves exactly like
+    /// `award_badge`.
+    pub fn award_badge_in_epoch(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+        epoch: u32,
+    ) -> Result<(), Error> {
+        let current = current_epoch(&env);
+        if epoch < current {
+            return Err(Error::EpochClosed);
+        }
+        if epoch > current {
+            return Err(Error::InvalidInput);
+        }
+        Self::award_badge(env, admin, user, badge_id)
+    }
+
+    /// Close the current season and start the next one. Admin only.
+    /// Returns the new epoch; every earlier epoch is closed for good.
+    pub fn advance_epoch(env: Env, admin: Address) -> Result<u32, Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        let epoch = current_epoch(&env).checked_add(1).ok_or(Error::Overflow)?;
+        env.storage().instance().set(&DataKey::CurrentEpoch, &epoch);
+
+        EpochAdvanced { epoch }.publish(&env);
+
+        Ok(epoch)
+    }
+
+    /// Return the current season, starting at 0.
+    pub fn current_epoch(env: Env) -> u32 {
+        current_epoch(&env)
+    }
+
+    /// Whether `epoch` was closed by `advance_epoch`.
+    pub fn is_epoch_closed(env: Env, epoch: u32) -> bool {
+        epoch < current_epoch(&env)
+    }
+
+    /// Award `badge_id` to every address in `users`. Admin only.
+    ///
+    /// Each award runs the same checks as `award_badge` with
+    /// `AwardReason::Manual`; any failure aborts the whole batch. `users`
+    /// must hold 1 to `MAX_PAGE_SIZE` addresses, otherwise `InvalidInput`.
+    /// Events follow `batch_event_mode`. Returns the number of awards.
+    pub fn award_badge_batch(
+        env: Env,
+        admin: Address,
+        users: Vec<Address>,
+        badge_id: u64,
+    ) -> Result<u32, Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        let count = users.len();
+        if count == 0 || count > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
+        }
+
+        let mode = batch_event_mode(&env);
+        for user in users.iter() {
+            let badge = store_award(
+                &env,
+                &user,
+                badge_id,
+                AwardReason::Manual,
+                no_note(&env),
+                None,
+            )?;
+            record_issuer_award(&env, &admin);
+            if mode != BatchEventMode::Summary {
+                publish_awarded(
+                    &env,
+                    &user,
+                    badge_id,
+                    AwardReason::Manual,
+                    &badge,
+                    no_note(&env),
+                );
+            }
+            publish_reward_due(&env, &user, badge_id, &badge);
+            notify_subscriber(&env, &user, badge_id);
+        }
+
+        if mode != BatchEventMode::PerItem {
+            BatchAwarded {
+                badge_id,
+                count,
+                first_user: users.first().unwrap(),
+                last_user: users.last().unwrap(),
+            }
+            .publish(&env);
+        }
+
+        Ok(count)
+    }
+
+    /// Remove `badge_id` from every address in `users` that holds it, for
+    /// mass corrections. Admin only.
+    ///
+    /// Users without the badge are skipped. `users` must hold 1 to
+    /// `MAX_PAGE_SIZE` addresses (`InvalidInput`) and the badge must exist
+    /// (`BadgeNotFound`). Each revocation drops the holding, award record,
+    /// ordinal, award source, and any vesting reward, and frees a unit of
+    /// supply; rewards already credited to the claimable balance are not
+    /// clawed back. Returns the users actually revoked, in input order.
+    pub fn revoke_badge_batch(
+        env: Env,
+        admin: Address,
+        users: Vec<Address>,
+        badge_id: u64,
+    ) -> Result<Vec<Address>, Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        if users.is_empty() || users.len() > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
+        }
+        require_badge_exists(&env, badge_id)?;
+
+        let mut revoked = vec![&env];
+        for user in users.iter() {
+            if revoke(&env, &user, badge_id) {
+                BadgeRevoked {
+                    user: user.clone(),
+                    badge_id,
+                }
+                .publish(&env);
+                revoked.push_back(user);
+            }
+        }
+
+        if !revoked.is_empty() {
+            let count_key = DataKey::HolderCount(badge_id);
+            let holders = holder_count(&env, badge_id).saturating_sub(revoked.len());
+            env.storage().persistent().set(&count_key, &holders);
+            bump(&env, &count_key);
+            add_stat(&env, DataKey::HoldingCount, -i64::from(revoked.len()));
+        }
+
+        Ok(revoked)
+    }
+
+    /// Number of badges `issuer` has awarded through the admin award paths.
+    ///
+    /// Self-service claims (`claim_badge`, `claim_meta`, `accept_badge`) have
+    /// no issuer and are not counted.
+    pub fn issuer_stats(env: Env, issuer: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IssuerAwardCount(issuer))
+            .unwrap_or(0)
+    }
+
+    /// Ledgers left before `user` may earn another badge in `family`.
+    ///
+    /// The cooldown is the one configured on the badge the user most recently
+    /// earned in that family. Returns 0 when no cooldown applies.
+    pub fn cooldown_remaining(env: Env, user: Address, family: u32) -> u32 {
+        cooldown_remaining(&env, &user, family)
+    }
+
+    /// Times `user` has earned `badge_id`: the stack count for a stackable
+    /// badge, otherwise 1 if held. 0 if not held or the user is private.
+    pub fn badge_quantity(env: Env, user: Address, badge_id: u64) -> u32 {
+        if !visible_holdings(&env, &user).contains(badge_id) {
+            return 0;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::BadgeStack(user, badge_id))
+            .unwrap_or(1)
+    }
+
+    /// Number of users holding `badge_id`.
+    pub fn holder_count(env: Env, badge_id: u64) -> u32 {
+        holder_count(&env, badge_id)
+    }
+
+    /// Counts of persistent entries by kind, for projecting TTL-bump and rent
+    /// costs. Reads only instance counters, so the cost does not grow with
+    /// the number of badges.
+    pub fn storage_stats(env: Env) -> StorageStats {
+        StorageStats {
+            badges: stat(&env, &DataKey::BadgeCount) as u32,
+            meta_badges: stat(&env, &DataKey::MetaBadgeCount) as u32,
+            holdings: stat(&env, &DataKey::HoldingCount),
+            awards_issued: env
+                .storage()
+                .instance()
+                .get(&DataKey::AwardOrdinal)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Awards left before `badge_id` sells out, or `None` if it is unlimited
+    /// or undefined.
+    pub fn remaining_supply(env: Env, badge_id: u64) -> Option<u32> {
+        let badge = require_badge_exists(&env, badge_id).ok()?;
+        badge
+            .max_supply
+            .map(|max| max.saturating_sub(holder_count(&env, badge_id)))
+    }
+
+    /// Up to `n` (capped at `MAX_PAGE_SIZE`) `(user, points)` pairs from the
+    /// global points leaderboard, highest first. A user's points are the
+    /// number of distinct badges they hold, so restacking a stackable badge
+    /// earns none. Private users are left out.
+    ///
+    /// The board is approximate. Holders cannot be enumerated on-chain, so a
+    /// user who fell off it is only reconsidered when their own holdings next
+    /// change; after a demotion the board may be short or miss a user who now
+    /// outranks its tail.
+    pub fn top_holders(env: Env, n: u32) -> Vec<(Address, u32)> {
+        let board: Vec<(Address, u32)> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PointsLeaderboard)
+            .unwrap_or_else(|| vec![&env]);
+
+        let mut top = vec![&env];
+        for (user, points) in board.iter() {
+            if top.len() >= n.min(MAX_PAGE_SIZE) {
+                break;
+            }
+            if !is_private(&env, &user) {
+                top.push_back((user, points));
+            }
+        }
+        top
+    }
+
+    // -----------------------------------------------------------------------
+    // set_badge_verifier / award_if_eligible
+    // -----------------------------------------------------------------------
+
+    /// Set or clear the external verifier for `badge_id`. Admin only.
+    ///
+    /// Lets eligibility logic evolve without redeploying this contract; the
+    /// criteria commitment itself is unchanged.
+    pub fn set_badge_verifier(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        verifier: Option<Address>,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        let mut badge = require_badge_exists(&env, badge_id)?;
+        badge.verifier = verifier.clone();
+
+        let key = DataKey::Badge(badge_id);
+        env.storage().persistent().set(&key, &badge);
+        bump(&env, &key);
+
+        VerifierSet { badge_id, verifier }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Set or clear the contract notified when `badge_id` is awarded. Admin
+    /// only.
+    ///
+    /// After each award's state is written, the subscriber's
+    /// `on_badge_awarded(user, badge_id)` is invoked. A failing subscriber
+    /// does not revert the award; the outcome is reported in
+    /// `SubscriberNotified`.
+    pub fn set_badge_subscriber(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        subscriber: Option<Address>,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        require_badge_exists(&env, badge_id)?;
+
+        let key = DataKey::BadgeSubscriber(badge_id);
+        match subscriber {
+            Some(subscriber) => {
+                env.storage().persistent().set(&key, &subscriber);
+                bump(&env, &key);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+
+        Ok(())
+    }
+
+    /// Return the contract notified when `badge_id` is awarded, if any.
+    pub fn badge_subscriber(env: Env, badge_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BadgeSubscriber(badge_id))
+    }
+
+    /// Vest `badge_id`'s reward instead of paying it at once. Admin only.
+    ///
+    /// Later awards record a `VestingEntry` that vests linearly over
+    /// `duration_ledgers` from the award, with nothing claimable for the
+    /// first `cliff_ledgers`; claim with `claim_vested`. `duration_ledgers`
+    /// must be non-zero and at least `cliff_ledgers` (`InvalidInput`).
+    /// Stackable badges cannot vest (`InvalidInput`), since a user holds one
+    /// vesting entry per badge. Existing awards keep the schedule they were
+    /// made under.
+    pub fn set_badge_vesting(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        cliff_ledgers: u32,
+        duration_ledgers: u32,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        let badge = require_badge_exists(&env, badge_id)?;
+
+        if badge.stackable || duration_ledgers == 0 || cliff_ledgers > duration_ledgers {
+            return Err(Error::InvalidInput);
+        }
+
+        let key = DataKey::BadgeVesting(badge_id);
+        env.storage()
+            .persistent()
+            .set(&key, &(cliff_ledgers, duration_ledgers));
+        bump(&env, &key);
+
+        Ok(())
+    }
+
+    /// Return `badge_id`'s `(cliff_ledgers, duration_ledgers)`, if vesting.
+    pub fn badge_vesting(env: Env, badge_id: u64) -> Option<(u32, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BadgeVesting(badge_id))
+    }
+
+    /// Transfer the vested, unclaimed part of `user`'s reward for
+    /// `badge_id`. User must authorize.
+    ///
+    /// Returns `NothingToClaim` if no vesting entry exists or nothing new
+    /// has vested, and `RewardTokenNotSet` if the entry has no token
+    /// override and no default is set. Returns the amount paid.
+    pub fn claim_vested(env: Env, user: Address, badge_id: u64) -> Result<i128, Error> {
+        require_initialized(&env)?;
+        require_auth(&user);
+
+        let key = DataKey::Vesting(user.clone(), badge_id);
+        let mut entry: VestingEntry = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::NothingToClaim)?;
+        let amount = entry.vested_at(env.ledger().sequence()) - entry.claimed;
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        let token = entry
+            .token
+            .clone()
+            .or_else(|| env.storage().instance().get(&DataKey::RewardToken))
+            .ok_or(Error::RewardTokenNotSet)?;
+
+        entry.claimed += amount;
+        env.storage().persistent().set(&key, &entry);
+        bump(&env, &key);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &user, &amount);
+
+        RewardClaimed {
+            user,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Return `user`'s vesting entry for `badge_id`, if any.
+    pub fn vesting_of(env: Env, user: Address, badge_id: u64) -> Option<VestingEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Vesting(user, badge_id))
+    }
+
+    /// Award `badge_id` to `user` if the badge's verifier accepts `proof`.
+    /// Admin only.
+    ///
+    /// Calls `verifier.verify(user, proof)` and returns `NotEligible` when it
+    /// answers `false`. Without a verifier the badge is awarded directly. All
+    /// regular award checks still apply.
+    pub fn award_if_eligible(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+        proof: Bytes,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        let badge = require_badge_exists(&env, badge_id)?;
+        if let Some(verifier) = badge.verifier {
+            if !CriteriaVerifierClient::new(&env, &verifier).verify(&user, &proof) {
+                return Err(Error::NotEligible);
+            }
+        }
+
+        award(&env, &user, badge_id, AwardReason::Automated, no_note(&env))?;
+        record_issuer_award(&env, &admin);
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // offer_badge / accept_badge / decline_badge
+    // -----------------------------------------------------------------------
+
+    /// Offer `badge_id` to `user` instead of awarding it outright. Admin only.
+    ///
+    /// Nothing is added to the user's holdings until they call
+    /// `accept_badge`. Re-offering a pending badge is a no-op.
+    pub fn offer_badge(
+        env: Env,
+        admin: Address,
+        user: Address,
+        badge_id: u64,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        let badge = require_badge_exists(&env, badge_id)?;
+
+        let key = DataKey::PendingBadge(user.clone(), badge_id);
+        if env.storage().persistent().has(&key) {
+            return Ok(());
+        }
+        env.storage().persistent().set(&key, &true);
+        bump(&env, &key);
+        add_offer_count(&env, badge_id, 1);
+
+        let pending_key = DataKey::UserPending(user.clone());
+        let mut pending: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .unwrap_or_else(|| vec![&env]);
+        pending.push_back(badge_id);
+        env.storage().persistent().set(&pending_key, &pending);
+        bump(&env, &pending_key);
+
+        if emits(&env, EventLevel::Standard) {
+            BadgeOffered {
+                user: user.clone(),
+                badge_id,
+            }
+            .publish(&env);
+        }
+
+        if badge.reward > 0 {
+            let reward_key = DataKey::PendingReward(user.clone(), badge_id);
+            env.storage().persistent().set(&reward_key, &badge.reward);
+            bump(&env, &reward_key);
+            RewardEscrowed {
+                user,
+                badge_id,
+                amount: badge.reward,
+            }
+            .publish(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Accept a pending offer, awarding the badge and releasing any reward
+    /// escrowed with it. User must authorize.
+    ///
+    /// Returns `NoPendingOffer` if nothing was offered. All award checks,
+    /// including the duplicate guard, run now rather than at offer time. The
+    /// award pays exactly the escrowed amount (nothing if none was escrowed)
+    /// through the usual settlement path. The definition cannot be deleted
+    /// while the offer is pending.
+    pub fn accept_badge(env: Env, user: Address, badge_id: u64) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_auth(&user);
+
+        let key = DataKey::PendingBadge(user.clone(), badge_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::NoPendingOffer);
+        }
+
+        let escrowed = take_pending_reward(&env, &user, badge_id);
+        award_paying(
+            &env,
+            &user,
+            badge_id,
+            AwardReason::Manual,
+            no_note(&env),
+            Some(escrowed.unwrap_or(0)),
+        )?;
+        env.storage().persistent().remove(&key);
+        unindex(&env, DataKey::UserPending(user.clone()), badge_id);
+        add_offer_count(&env, badge_id, -1);
+
+        if emits(&env, EventLevel::Standard) {
+            BadgeAccepted {
+                user: user.clone(),
+                badge_id,
+            }
+            .publish(&env);
+        }
+
+        if let Some(amount) = escrowed {
+            RewardReleased {
+                user,
+                badge_id,
+                amount,
+            }
+            .publish(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Decline a pending offer, forfeiting any reward escrowed with it. User
+    /// must authorize.
+    ///
+    /// Returns `NoPendingOffer` if nothing was offered.
+    pub fn decline_badge(env: Env, user: Address, badge_id: u64) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_auth(&user);
+
+        let key = DataKey::PendingBadge(user.clone(), badge_id);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::NoPendingOffer);
+        }
+        env.storage().persistent().remove(&key);
+        unindex(&env, DataKey::UserPending(user.clone()), badge_id);
+        add_offer_count(&env, badge_id, -1);
+
+        if emits(&env, EventLevel::Standard) {
+            BadgeDeclined {
+                user: user.clone(),
+                badge_id,
+            }
+            .publish(&env);
+        }
+
+        if let Some(amount) = take_pending_reward(&env, &user, badge_id) {
+            RewardForfeited {
+                user,
+                badge_id,
+                amount,
+            }
+            .publish(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Return whether `badge_id` is currently offered to `user`.
+    pub fn is_offered(env: Env, user: Address, badge_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::PendingBadge(user, badge_id))
+    }
+
+    /// Badge IDs offered to `user` and awaiting acceptance, oldest first.
+    /// Empty if there are none.
+    pub fn pending_offers(env: Env, user: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserPending(user))
+            .unwrap_or_else(|| vec![&env])
+    }
+
+    /// Reward held back for `user`'s pending offer of `badge_id`; 0 if none.
+    pub fn escrowed_reward(env: Env, user: Address, badge_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingReward(user, badge_id))
+            .unwrap_or(0)
+    }
+
+    // -----------------------------------------------------------------------
+    // rewards
+    // -----------------------------------------------------------------------
+
+    /// Set the token paid out by `claim_rewards`. Admin only.
+    ///
+    /// The contract must hold enough of `token` to cover outstanding claims.
+    pub fn set_reward_token(env: Env, admin: Address, token: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        env.storage().instance().set(&DataKey::RewardToken, &token);
+
+        Ok(())
+    }
+
+    /// Set the tournament contract that awards of tournament-scoped badges
+    /// check participation against. Admin only.
+    pub fn set_tournament_contract(
+        env: Env,
+        admin: Address,
+        contract: Address,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TournamentContract, &contract);
+
+        Ok(())
+    }
+
+    /// Return the configured tournament contract, if any.
+    pub fn tournament_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::TournamentContract)
+    }
+
+    /// Pause awards. Admin only.
+    ///
+    /// While paused, every award path returns `ContractPaused`; definitions,
+    /// offers, claims, and reads keep working.
+    pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        if is_paused(&env) {
+            return Err(Error::AlreadyPaused);
+        }
+        env.storage().instance().set(&DataKey::Paused, &true);
+
+        Paused { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Lift a pause. Admin only.
+    pub fn unpause(env: Env, admin: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        if !is_paused(&env) {
+            return Err(Error::NotPaused);
+        }
+        env.storage().instance().remove(&DataKey::Paused);
+
+        Unpaused { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Whether awards are paused.
+    pub fn is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
+
+    /// Choose whether awards credit claimable balances (`EventDriven`) or
+    /// pay rewards on the spot (`Atomic`). Admin only, and only while paused
+    /// (`NotPaused` otherwise) so no award straddles the switch. `Atomic`
+    /// requires a reward token (`RewardTokenNotSet`).
+    ///
+    /// Balances credited before a switch stay claimable in either mode.
+    pub fn set_settlement_mode(
+        env: Env,
+        admin: Address,
+        mode: SettlementMode,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        if !is_paused(&env) {
+            return Err(Error::NotPaused);
+        }
+
+        if mode == SettlementMode::Atomic && !env.storage().instance().has(&DataKey::RewardToken) {
+            return Err(Error::RewardTokenNotSet);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::SettlementMode, &mode);
+
+        SettlementModeSet { mode }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the settlement mode. Defaults to `EventDriven`.
+    pub fn settlement_mode(env: Env) -> SettlementMode {
+        settlement_mode(&env)
+    }
+
+    /// Return the token paid out by `claim_rewards`, if set.
+    pub fn reward_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::RewardToken)
+    }
+
+    /// Transfer every reward `user` has earned and not yet claimed. User must
+    /// authorize.
+    ///
+    /// Awards only credit the claimable balance, so a failing payout can
+    /// never block an award. The balance is cleared before the transfer so a
+    /// re-entrant call finds nothing to claim. Returns the amount paid.
+    pub fn claim_rewards(env: Env, user: Address) -> Result<i128, Error> {
+        require_initialized(&env)?;
+        require_auth(&user);
+
+        let key = DataKey::Claimable(user.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return Err(Error::NothingToClaim);
+        }
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RewardToken)
+            .ok_or(Error::RewardTokenNotSet)?;
+
+        env.storage().persistent().remove(&key);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &user, &amount);
+
+        RewardClaimed {
+            user,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Like `claim_rewards`, for rewards of badges whose `reward_token`
+    /// override is `token`. User must authorize.
+    pub fn claim_rewards_in(env: Env, user: Address, token: Address) -> Result<i128, Error> {
+        require_initialized(&env)?;
+        require_auth(&user);
+
+        let key = DataKey::ClaimableIn(user.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        env.storage().persistent().remove(&key);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &user, &amount);
+
+        RewardClaimed {
+            user,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Return this contract's balance of `token`, e.g. to check it covers
+    /// outstanding claims. Works for any token contract; no auth required.
+    pub fn contract_balance(env: Env, token: Address) -> i128 {
+        TokenClient::new(&env, &token).balance(&env.current_contract_address())
+    }
+
+    /// Top up this contract's balance of `token` from `from`, e.g. so
+    /// atomic awards can pay out. `from` must authorize.
+    pub fn fund_rewards(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_auth(&from);
+        if amount <= 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        TokenClient::new(&env, &token).transfer(&from, env.current_contract_address(), &amount);
+
+        RewardsFunded {
+            from,
+            token,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the rewards `user` has earned and not yet claimed.
+    pub fn claimable(env: Env, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claimable(user))
+            .unwrap_or(0)
+    }
+
+    /// Return the unclaimed rewards `user` has earned in override `token`.
+    pub fn claimable_in(env: Env, user: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimableIn(user, token))
+            .unwrap_or(0)
+    }
+
+    // -----------------------------------------------------------------------
+    // max_badges_per_user
+    // -----------------------------------------------------------------------
+
+    /// Cap the number of badges any single user may hold. Admin only.
+    ///
+    /// `max` of 0 disables the cap. Lowering the cap below a user's current
+    /// holdings does not revoke anything; it only blocks further awards.
+    pub fn set_max_badges_per_user(env: Env, admin: Address, max: u32) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxBadgesPerUser, &max);
+
+        Ok(())
+    }
+
+    /// Enable or disable `StorageBumped` events on persistent writes at the
+    /// `Standard` event level. Admin only. Off by default to avoid event
+    /// spam; `Verbose` emits them regardless and `Minimal` never does.
+    pub fn set_verbose_storage_events(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::VerboseStorageEvents, &enabled);
+
+        Ok(())
+    }
+
+    /// Choose which events `award_badge_batch` emits. Admin only.
+    pub fn set_batch_event_mode(
+        env: Env,
+        admin: Address,
+        mode: BatchEventMode,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::BatchEventMode, &mode);
+
+        Ok(())
+    }
+
+    /// Choose how much event detail the contract emits. Admin only.
+    pub fn set_event_level(env: Env, admin: Address, level: EventLevel) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        env.storage().instance().set(&DataKey::EventLevel, &level);
+
+        Ok(())
+    }
+
+    /// Return the event level. Defaults to `Standard`.
+    pub fn event_level(env: Env) -> EventLevel {
+        event_level(&env)
+    }
+
+    /// Return the batch event mode. Defaults to `PerItem`.
+    pub fn batch_event_mode(env: Env) -> BatchEventMode {
+        batch_event_mode(&env)
+    }
+
+    /// Return the per-user badge cap. 0 means unlimited.
+    pub fn max_badges_per_user(env: Env) -> u32 {
+        max_badges_per_user(&env)
+    }
+
+    // -----------------------------------------------------------------------
+    // badges_of
+    // -----------------------------------------------------------------------
+
+    /// Return the ledger of `user`'s latest award, or `None` if they were
+    /// never awarded a badge or are private.
+    pub fn last_active(env: Env, user: Address) -> Option<u32> {
+        if is_private(&env, &user) {
+            return None;
+        }
+        env.storage().persistent().get(&DataKey::LastActive(user))
+    }
+
+    /// Return the position of `user`'s award of `badge_id` in the global
+    /// issuance order, starting at 1. Unlike the award ledger, ordinals never
+    /// collide. `None` if the user does not hold the badge, is private, or it
+    /// was awarded before ordinals were recorded.
+    pub fn award_ordinal(env: Env, user: Address, badge_id: u64) -> Option<u64> {
+        if is_private(&env, &user) {
+            return None;
+        }
+        env.storage()
+            .persistent()
+            .get(&DataKey::AwardOrdinalOf(user, badge_id))
+    }
+
+    /// Return the list of badge IDs awarded to `user`.
+    ///
+    /// Returns an empty list if the user has not been awarded any badges or
+    /// has opted out of public display with `set_private`; use
+    /// `badges_of_as` to read a private user's holdings. Does not require
+    /// initialization — a user with no badges trivially has an empty list
+    /// regardless of contract state.
+    pub fn badges_of(env: Env, user: Address) -> Vec<u64> {
+        visible_holdings(&env, &user)
+    }
+
+    /// Return the badges `user` holds that are scoped to tournament
+    /// `tournament_id`, in award order. Empty for private users.
+    pub fn badges_of_tournament(env: Env, user: Address, tournament_id: u64) -> Vec<u64> {
+        let mut scoped = vec![&env];
+        for badge_id in Self::badges_of(env.clone(), user).iter() {
+            let badge: Option<BadgeDefinition> =
+                env.storage().persistent().get(&DataKey::Badge(badge_id));
+            if badge.is_some_and(|badge| badge.scope == BadgeScope::Tournament(tournament_id)) {
+                scoped.push_back(badge_id);
+            }
+        }
+        scoped
+    }
+
+    /// Like `badges_of`, but a private user's real holdings are returned when
+    /// `viewer` is the user or the admin. `viewer` must authorize.
+    pub fn badges_of_as(env: Env, viewer: Address, user: Address) -> Vec<u64> {
+        require_auth(&viewer);
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if viewer == user || admin.is_some_and(|admin| admin == viewer) {
+            return holdings(&env, &user);
+        }
+        Self::badges_of(env, user)
+    }
+
+    /// Hide (or show again) `user`'s holdings from every public getter that
+    /// reports them, which then answers as if the user held nothing. User
+    /// must authorize. Awards, duplicate checks, and eligibility checks keep
+    /// using the real holdings.
+    pub fn set_private(env: Env, user: Address, private: bool) {
+        require_auth(&user);
+        let key = DataKey::Private(user.clone());
+        if private {
+            env.storage().persistent().set(&key, &true);
+            bump(&env, &key);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        if emits(&env, EventLevel::Standard) {
+            PrivacySet { user, private }.publish(&env);
+        }
+    }
+
+    /// Whether `user` has hidden their holdings with `set_private`.
+    pub fn is_private(env: Env, user: Address) -> bool {
+        is_private(&env, &user)
+    }
+
+    /// Return up to `limit` (capped at `MAX_PAGE_SIZE`) of `user`'s badges
+    /// from position `start` of their holdings, each joined to its
+    /// definition. Holdings whose definition no longer exists are skipped,
+    /// so a page may be shorter than `limit`.
+    pub fn badges_with_defs(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<(u64, BadgeDefinition)> {
+        let held = Self::badges_of(env.clone(), user);
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(held.len());
+
+        let mut page = vec![&env];
+        for i in start..end {
+            let badge_id = held.get(i).unwrap();
+            if let Some(badge) = env.storage().persistent().get(&DataKey::Badge(badge_id)) {
+                page.push_back((badge_id, badge));
+            }
+        }
+        page
+    }
+
+    /// Return the badges `user` does not hold among up to `limit` (capped at
+    /// `MAX_PAGE_SIZE`) defined badges from position `start` of the badge
+    /// index. Pages cover index positions, not results, so a page may be
+    /// shorter than `limit`; request the next page at `start + limit`.
+    pub fn missing_badges(env: Env, user: Address, start: u32, limit: u32) -> Vec<u64> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BadgeIndex)
+            .unwrap_or_else(|| vec![&env]);
+        let held = visible_holdings(&env, &user);
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(ids.len());
+
+        let mut page = vec![&env];
+        for i in start..end {
+            let badge_id = ids.get(i).unwrap();
+            if !held.contains(badge_id) {
+                page.push_back(badge_id);
+            }
+        }
+        page
+    }
+
+    /// Return the award record of every badge `user` holds, in award order.
+    pub fn badges_of_detailed(env: Env, user: Address) -> Vec<AwardRecord> {
+        let mut records = vec![&env];
+        for badge_id in Self::badges_of(env.clone(), user.clone()).iter() {
+            let record = env
+                .storage()
+                .persistent()
+                .get(&DataKey::AwardRecord(user.clone(), badge_id))
+                .unwrap_or_else(|| AwardRecord {
+                    badge_id,
+                    awarded_ledger: 0,
+                    reason: AwardReason::Manual,
+                    note_hash: no_note(&env),
+                });
+            records.push_back(record);
+        }
+        records
+    }
+
+    /// Return the IDs of badges `user` was awarded between `from_ledger` and
+    /// `to_ledger` inclusive, in award order. Returns `InvalidInput` if
+    /// `from_ledger > to_ledger`.
+    pub fn awards_in_range(
+        env: Env,
+        user: Address,
+        from_ledger: u32,
+        to_ledger: u32,
+    ) -> Result<Vec<u64>, Error> {
+        if from_ledger > to_ledger {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut ids = vec![&env];
+        for record in Self::badges_of_detailed(env.clone(), user).iter() {
+            if (from_ledger..=to_ledger).contains(&record.awarded_ledger) {
+                ids.push_back(record.badge_id);
+            }
+        }
+        Ok(ids)
+    }
+
+    // -----------------------------------------------------------------------
+    // badges_by_hash
+    // -----------------------------------------------------------------------
+
+    /// Return the badge IDs defined with `criteria_hash`, in definition order.
+    ///
+    /// Returns an empty list when no badge uses the hash. More than one entry
+    /// usually indicates an accidental duplicate definition.
+    pub fn badges_by_hash(env: Env, criteria_hash: BytesN<32>) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::HashToBadges(criteria_hash))
+            .unwrap_or_else(|| vec![&env])
+    }
+
+    // -----------------------------------------------------------------------
+    // set_badge_metadata
+    // -----------------------------------------------------------------------
+
+    /// Attach human-readable metadata to an existing badge. Admin only.
+    ///
+    /// The badge must already be defined via `define_badge`. Metadata may be
+    /// updated by calling this again; each write extends the TTL.
+    ///
+    /// Keeping metadata separate from the immutable `BadgeDefinition` allows
+    /// copy edits and future metadata field additions without touching the
+    /// on-chain criteria commitment.
+    pub fn set_badge_metadata(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        title: String,
+        description: String,
+        award_rules: String,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        require_badge_exists(&env, badge_id)?;
+
+        let entry = BadgeMetaEntry {
+            title,
+            description,
+            award_rules,
+        };
+        let key = DataKey::BadgeMeta(badge_id);
+        env.storage().persistent().set(&key, &entry);
+        bump(&env, &key);
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // get_badge_summary
+    // -----------------------------------------------------------------------
+
+    /// Return a combined badge definition + metadata snapshot for `badge_id`.
+    ///
+    /// This is designed for single-call badge-card rendering — no additional
+    /// reads are required. When `badge_id` is unknown `found` is `false` and
+    /// all other fields carry zero/empty values. Missing metadata fields
+    /// (metadata not yet set) are returned as empty strings.
+    pub fn get_badge_summary(env: Env, badge_id: u64) -> BadgeSummary {
+        let definition: Option<BadgeDefinition> =
+            env.storage().persistent().get(&DataKey::Badge(badge_id));
+
+        match definition {
+            None => BadgeSummary {
+                found: false,
+                badge_id,
+                criteria_hash: BytesN::from_array(&env, &[0u8; 32]),
+                reward: 0,
+                title: String::from_str(&env, ""),
+                description: String::from_str(&env, ""),
+                award_rules: String::from_str(&env, ""),
+            },
+            Some(def) => {
+                let meta: Option<BadgeMetaEntry> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::BadgeMeta(badge_id));
+                let (title, description, award_rules) = match meta {
+                    Some(m) => (m.title, m.description, m.award_rules),
+                    None => (
+                        String::from_str(&env, ""),
+                        String::from_str(&env, ""),
+                        String::from_str(&env, ""),
+                    ),
+                };
+                BadgeSummary {
+                    found: true,
+                    badge_id,
+                    criteria_hash: def.criteria_hash,
+                    reward: def.reward,
+                    title,
+                    description,
+                    award_rules,
+                }
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // get_claim_status
+    // -----------------------------------------------------------------------
+
+    /// Return the claim-status snapshot for `(user, badge_id)`.
+    ///
+    /// `badge_found` is `false` and `claimed` is `false` when the badge does
+    /// not exist. `claimed` is `false` when the badge exists but has not been
+    /// awarded to this user, or the user is private. Both fields are
+    /// deterministic for all inputs.
+    pub fn get_claim_status(env: Env, user: Address, badge_id: u64) -> ClaimStatusSnapshot {
+        let badge_found = env.storage().persistent().has(&DataKey::Badge(badge_id));
+
+        if !badge_found {
+            return ClaimStatusSnapshot {
+                badge_id,
+                claimed: false,
+                badge_found: false,
+            };
+        }
+
+        let claimed = visible_holdings(&env, &user).contains(badge_id);
+
+        ClaimStatusSnapshot {
+            badge_id,
+            claimed,
+            badge_found: true,
+        }
+    }
+
+    /// Return whether `user` holds `badge_id`, distinguishing a user with no
+    /// `UserBadges` entry (`NoRecord`) from one whose holdings simply lack
+    /// the badge (`DoesNotHold`). Does not check that the badge is defined.
+    /// Private users report `NoRecord`.
+    pub fn holds_badge_status(env: Env, user: Address, badge_id: u64) -> HoldStatus {
+        if is_private(&env, &user) {
+            return HoldStatus::NoRecord;
+        }
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Vec<u64>>(&DataKey::UserBadges(user))
+        {
+            None => HoldStatus::NoRecord,
+            Some(badges) if badges.contains(badge_id) => HoldStatus::Holds,
+            Some(_) => HoldStatus::DoesNotHold,
+        }
+    }
+
+    /// Return whether `user` holds `badge_id`, ignoring `set_private`. Meant
+    /// for contracts that skip duplicate awards, which must see the real
+    /// holdings; display code should use `badges_of`.
+    pub fn holds_badge(env: Env, user: Address, badge_id: u64) -> bool {
+        holdings(&env, &user).contains(badge_id)
+    }
+
+    // -----------------------------------------------------------------------
+    // check_invariants
+    // -----------------------------------------------------------------------
+
+    /// Verify that stored state is internally consistent. Read-only.
+    ///
+    /// Checks the admin/renounced config, that the `storage_stats` badge
+    /// counter matches the badge index length and is at least the meta-badge
+    /// counter, and, for up to `limit` (capped at `MAX_PAGE_SIZE`) badges
+    /// from position `start` of the badge index, that each definition exists
+    /// once and is well-formed, that its holder count is within
+    /// `max_supply`, that the criteria-hash index points back to it, and that
+    /// any meta-badge prerequisites are defined. A page covering the whole
+    /// index also checks the meta-badge counter exactly. Check the whole set
+    /// page by page at `start + limit`.
+    ///
+    /// Users cannot be enumerated, so reward balances are checked for the
+    /// up to `MAX_PAGE_SIZE` `users` passed (`InvalidInput` beyond that):
+    /// claimable and escrowed rewards are non-negative, each pending offer
+    /// is recorded, holdings have no duplicates, and each vesting entry has
+    /// `0 <= claimed <= total`. Returns `InvariantViolation` on the first
+    /// mismatch.
+    pub fn check_invariants(
+        env: Env,
+        start: u32,
+        limit: u32,
+        users: Vec<Address>,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        if users.len() > MAX_PAGE_SIZE {
+            return Err(Error::InvalidInput);
+        }
+
+        if env.storage().instance().has(&DataKey::Admin) == is_admin_renounced(&env) {
+            return Err(Error::InvariantViolation);
+        }
+
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BadgeIndex)
+            .unwrap_or_else(|| vec![&env]);
+        let meta_badges = stat(&env, &DataKey::MetaBadgeCount);
+        if stat(&env, &DataKey::BadgeCount) != u64::from(ids.len())
+            || meta_badges > u64::from(ids.len())
+        {
+            return Err(Error::InvariantViolation);
+        }
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(ids.len());
+
+        let mut page_meta_badges = 0u64;
+        for i in start..end {
+            let badge_id = ids.get(i).unwrap();
+            if ids.first_index_of(badge_id) != Some(i) {
+                return Err(Error::InvariantViolation);
+            }
+
+            let badge =
+                require_badge_exists(&env, badge_id).map_err(|_| Error::InvariantViolation)?;
+            validate_definition(&badge).map_err(|_| Error::InvariantViolation)?;
+            if badge
+                .max_supply
+                .is_some_and(|max| holder_count(&env, badge_id) > max)
+            {
+                return Err(Error::InvariantViolation);
+            }
+
+            if !Self::badges_by_hash(env.clone(), badge.criteria_hash).contains(badge_id) {
+                return Err(Error::InvariantViolation);
+            }
+
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::MetaBadge(badge_id))
+            {
+                page_meta_badges += 1;
+            }
+            for prerequisite in Self::meta_requirements(env.clone(), badge_id).iter() {
+                if !env
+                    .storage()
+                    .persistent()
+                    .has(&DataKey::Badge(prerequisite))
+                {
+                    return Err(Error::InvariantViolation);
+                }
+            }
+        }
+        if start == 0 && end == ids.len() && page_meta_badges != meta_badges {
+            return Err(Error::InvariantViolation);
+        }
+
+        for user in users.iter() {
+            check_user_rewards(&env, &user)?;
+        }
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // recompute_holders
+    // -----------------------------------------------------------------------
+
+    /// Reset `badge_id`'s holder count to the number of distinct `users`
+    /// whose holdings list it. Admin only; the maintenance counterpart to
+    /// `check_invariants`.
+    ///
+    /// A full scan of holders is infeasible on-chain, so the caller supplies
+    /// the candidate set and must include every holder. Duplicates are
+    /// counted once. Returns the repaired count.
+    pub fn recompute_holders(
+        env: Env,
+        admin: Address,
+        badge_id: u64,
+        users: Vec<Address>,
+    ) -> Result<u32, Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+        require_badge_exists(&env, badge_id)?;
+
+        let mut counted: Vec<Address> = vec![&env];
+        for user in users.iter() {
+            if !counted.contains(&user) && holdings(&env, &user).contains(badge_id) {
+                counted.push_back(user);
+            }
+        }
+
+        let previous = holder_count(&env, badge_id);
+        let holders = counted.len();
+        let key = DataKey::HolderCount(badge_id);
+        env.storage().persistent().set(&key, &holders);
+        bump(&env, &key);
+        add_stat(
+            &env,
+            DataKey::HoldingCount,
+            i64::from(holders) - i64::from(previous),
+        );
+
+        HolderCountRepaired {
+            badge_id,
+            previous,
+            holders,
+        }
+        .publish(&env);
+
+        Ok(holders)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// A renounced contract still counts as initialized; `require_admin` is what
+/// rejects it.
+fn require_initialized(env: &Env) -> Result<(), Error> {
+    if !env.storage().instance().has(&DataKey::Admin) && !is_admin_renounced(env) {
+        return Err(Error::NotInitialized);
+    }
+    Ok(())
+}
+
+fn is_admin_renounced(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::AdminRenounced)
+}
+
+/// Verify that `caller` is the stored admin and has signed the invocation.
+///
+/// Returns `AdminRenounced` once the admin role has been burned.
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    if is_admin_renounced(env) {
+        return Err(Error::AdminRenounced);
+    }
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    require_auth(caller);
+    if caller != &admin && !cfg!(feature = "test-bypass-auth") {
+        return Err(Error::NotAuthorized);
+    }
+    Ok(())
+}
+
+/// `addr.require_auth()`, compiled out under the `test-bypass-auth` feature.
+/// Every auth check in the contract goes through here.
+fn require_auth(addr: &Address) {
+    #[cfg(not(feature = "test-bypass-auth"))]
+    addr.require_auth();
+    #[cfg(feature = "test-bypass-auth")]
+    let _ = addr;
+}
+
+/// Reject definitions with a negative reward, an empty award window, or a
+/// cooldown outside any family.
+fn validate_definition(definition: &BadgeDefinition) -> Result<(), Error> {
+    if definition.reward < 0 {
+        return Err(Error::InvalidInput);
+    }
+    if let AwardWindow::Between(start, end) = definition.award_window {
+        if start >= end {
+            return Err(Error::InvalidInput);
+        }
+    }
+    if definition.cooldown_ledgers > 0 && definition.badge_family == 0 {
+        return Err(Error::InvalidInput);
+    }
+    if definition
+        .name
+        .as_ref()
+        .is_some_and(|name| name.is_empty() || name.len() > MAX_NAME_LEN)
+    {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Validate `definition` and store it under a fresh `badge_id`.
+///
+/// A `reward_token` override must answer the token interface's `decimals`,
+/// otherwise `InvalidInput`.
+fn define(env: &Env, badge_id: u64, definition: BadgeDefinition) -> Result<(), Error> {
+    validate_definition(&definition)?;
+    if let Some(token) = &definition.reward_token {
+        if !matches!(TokenClient::new(env, token).try_decimals(), Ok(Ok(_))) {
+            return Err(Error::InvalidInput);
+        }
+    }
+
+    let key = DataKey::Badge(badge_id);
+    if env.storage().persistent().has(&key) {
+        return Err(Error::BadgeAlreadyExists);
+    }
+
+    env.storage().persistent().set(&key, &definition);
+    bump(env, &key);
+
+    index_badge_hash(env, &definition.criteria_hash, badge_id);
+    index_badge(env, badge_id);
+    add_stat(env, DataKey::BadgeCount, 1);
+
+    BadgeDefined {
+        badge_id,
+        criteria_hash: definition.criteria_hash,
+        reward: definition.reward,
+        reward_token_decimals: reward_token_decimals(env),
+        name: definition.name,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+/// Run every award-time check for `(user, badge_id)` and, if they pass,
+/// append the badge to the user's holdings and emit `BadgeAwarded` (plus
+/// `RewardDue` for a rewarded badge).
+///
+/// Shared by all award paths so admin awards and self-service claims enforce
+/// identical rules. Callers are responsible for authorization.
+fn award(
+    env: &Env,
+    user: &Address,
+    badge_id: u64,
+    reason: AwardReason,
+    note_hash: BytesN<32>,
+) -> Result<(), Error> {
+    award_paying(env, user, badge_id, reason, note_hash, None)
+}
+
+/// `award`, paying `reward` instead of the definition's reward when set.
+fn award_paying(
+    env: &Env,
+    user: &Address,
+    badge_id: u64,
+    reason: AwardReason,
+    note_hash: BytesN<32>,
+    reward: Option<i128>,
+) -> Result<(), Error> {
+    let badge = store_award(env, user, badge_id, reason, note_hash.clone(), reward)?;
+    publish_awarded(env, user, badge_id, reason, &badge, note_hash);
+    publish_reward_due(env, user, badge_id, &badge);
+    notify_subscriber(env, user, badge_id);
+    Ok(())
+}
+
+/// `award_paying` without the event. Returns the awarded badge's definition,
+/// with `reward` set to the amount actually paid.
+fn store_award(
+    env: &Env,
+    user: &Address,
+    badge_id: u64,
+    reason: AwardReason,
+    note_hash: BytesN<32>,
+    reward: Option<i128>,
+) -> Result<BadgeDefinition, Error> {
+    require_not_paused(env)?;
+    let mut badge = require_badge_exists(env, badge_id)?;
+    if let Some(reward) = reward {
+        badge.reward = reward;
+    }
+
+    if let AwardWindow::Between(start, end) = badge.award_window {
+        let now = env.ledger().sequence();
+        if now < start || now > end {
+            return Err(Error::AwardWindowClosed);
+        }
+    }
+
+    if badge.require_evaluation
+        && !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Evaluated(user.clone(), badge_id))
+    {
+        return Err(Error::NotEvaluated);
+    }
+
+    if let BadgeScope::Tournament(tournament_id) = badge.scope {
+        require_joined(env, user, tournament_id)?;
+    }
+
+    // Guard against duplicate awards; stackable badges count them instead.
+    let user_key = DataKey::UserBadges(user.clone());
+    let mut badges: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&user_key)
+        .unwrap_or_else(|| vec![env]);
+
+    let restack = badges.contains(badge_id);
+    if restack && !badge.stackable {
+        return Err(Error::BadgeAlreadyAwarded);
+    }
+
+    if badge.badge_family != 0 && cooldown_remaining(env, user, badge.badge_family) > 0 {
+        return Err(Error::CooldownActive);
+    }
+
+    let stack_key = DataKey::BadgeStack(user.clone(), badge_id);
+    if restack {
+        let quantity: u32 = env.storage().persistent().get(&stack_key).unwrap_or(1);
+        let quantity = quantity.checked_add(1).ok_or(Error::Overflow)?;
+        let ordinal = env
+            .storage()
+            .instance()
+            .get::<_, u64>(&DataKey::AwardOrdinal)
+            .unwrap_or(0)
+            .checked_add(1)
+            .ok_or(Error::Overflow)?;
+
+        let payout = prepare_payout(env, user, badge_id, &badge)?;
+
+        env.storage().persistent().set(&stack_key, &quantity);
+        bump(env, &stack_key);
+        env.storage()
+            .instance()
+            .set(&DataKey::AwardOrdinal, &ordinal);
+        let active_key = DataKey::LastActive(user.clone());
+        env.storage()
+            .persistent()
+            .set(&active_key, &env.ledger().sequence());
+        bump(env, &active_key);
+        if badge.badge_family != 0 {
+            let family_key = DataKey::FamilyAward(user.clone(), badge.badge_family);
+            env.storage().persistent().set(
+                &family_key,
+                &(env.ledger().sequence(), badge.cooldown_ledgers),
+            );
+            bump(env, &family_key);
+        }
+
+        apply_payout(env, user, badge_id, &badge, payout);
+        return Ok(badge);
+    }
+
+    // Bound the holdings vector so reads of it stay cheap.
+    let max_badges = max_badges_per_user(env);
+    if max_badges > 0 && badges.len() >= max_badges {
+        return Err(Error::UserBadgeLimit);
+    }
+
+    let holders = holder_count(env, badge_id);
+    if badge.max_supply.is_some_and(|max| holders >= max) {
+        return Err(Error::SupplyExhausted);
+    }
+    let holders = holders.checked_add(1).ok_or(Error::Overflow)?;
+    let ordinal = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::AwardOrdinal)
+        .unwrap_or(0)
+        .checked_add(1)
+        .ok_or(Error::Overflow)?;
+
+    let payout = prepare_payout(env, user, badge_id, &badge)?;
+
+    badges.push_back(badge_id);
+    env.storage().persistent().set(&user_key, &badges);
+    bump(env, &user_key);
+    update_points_board(env, user, badges.len());
+
+    let count_key = DataKey::HolderCount(badge_id);
+    env.storage().persistent().set(&count_key, &holders);
+    bump(env, &count_key);
+    add_stat(env, DataKey::HoldingCount, 1);
+
+    let record_key = DataKey::AwardRecord(user.clone(), badge_id);
+    let record = AwardRecord {
+        badge_id,
+        awarded_ledger: env.ledger().sequence(),
+        reason,
+        note_hash,
+    };
+    env.storage().persistent().set(&record_key, &record);
+    bump(env, &record_key);
+
+    env.storage()
+        .instance()
+        .set(&DataKey::AwardOrdinal, &ordinal);
+    let ordinal_key = DataKey::AwardOrdinalOf(user.clone(), badge_id);
+    env.storage().persistent().set(&ordinal_key, &ordinal);
+    bump(env, &ordinal_key);
+
+    let active_key = DataKey::LastActive(user.clone());
+    env.storage()
+        .persistent()
+        .set(&active_key, &env.ledger().sequence());
+    bump(env, &active_key);
+
+    if badge.badge_family != 0 {
+        let family_key = DataKey::FamilyAward(user.clone(), badge.badge_family);
+        env.storage().persistent().set(
+            &family_key,
+            &(env.ledger().sequence(), badge.cooldown_ledgers),
+        );
+        bump(env, &family_key);
+    }
+
+    if badge.stackable {
+        env.storage().persistent().set(&stack_key, &1u32);
+        bump(env, &stack_key);
+    }
+
+    apply_payout(env, user, badge_id, &badge, payout);
+
+    Ok(badge)
+}
+
+/// How one award of a badge pays its reward, resolved before anything is
+/// written so a failed check leaves no trace.
+enum Payout {
+    None,
+    /// New claimable balance to store under the key.
+    Claimable(DataKey, i128),
+    /// `(cliff_ledgers, duration_ledgers)` of a vesting entry to record.
+    Vesting(u32, u32),
+    /// Token to transfer the reward in right away.
+    Atomic(Address),
+}
+
+/// Work out how an award of `badge` to `user` pays its reward. Resolves and
+/// fund-checks an atomic payout (`RewardTokenNotSet`, `IncompatibleReward`,
+/// `InsufficientFunds`).
+fn prepare_payout(
+    env: &Env,
+    user: &Address,
+    badge_id: u64,
+    badge: &BadgeDefinition,
+) -> Result<Payout, Error> {
+    if badge.reward <= 0 {
+        return Ok(Payout::None);
+    }
+    // Vesting rewards bypass the settlement mode entirely.
+    if let Some((cliff_ledgers, duration_ledgers)) = env
+        .storage()
+        .persistent()
+        .get::<_, (u32, u32)>(&DataKey::BadgeVesting(badge_id))
+    {
+        return Ok(Payout::Vesting(cliff_ledgers, duration_ledgers));
+    }
+    if settlement_mode(env) == SettlementMode::Atomic {
+        let token: Address = badge
+            .reward_token
+            .clone()
+            .or_else(|| env.storage().instance().get(&DataKey::RewardToken))
+            .ok_or(Error::RewardTokenNotSet)?;
+        require_compatible_reward(env, &token)?;
+        if TokenClient::new(env, &token).balance(&env.current_contract_address()) < badge.reward {
+            return Err(Error::InsufficientFunds);
+        }
+        return Ok(Payout::Atomic(token));
+    }
+    let claimable_key = match &badge.reward_token {
+        Some(token) => DataKey::ClaimableIn(user.clone(), token.clone()),
+        None => DataKey::Claimable(user.clone()),
+    };
+    let owed: i128 = env.storage().persistent().get(&claimable_key).unwrap_or(0);
+    let owed = owed.checked_add(badge.reward).ok_or(Error::Overflow)?;
+    Ok(Payout::Claimable(claimable_key, owed))
+}
+
+/// Pay `badge`'s reward to `user` as resolved by `prepare_payout`.
+fn apply_payout(env: &Env, user: &Address, badge_id: u64, badge: &BadgeDefinition, payout: Payout) {
+    match payout {
+        Payout::None => {}
+        Payout::Claimable(key, owed) => {
+            env.storage().persistent().set(&key, &owed);
+            bump(env, &key);
+        }
+        Payout::Vesting(cliff_ledgers, duration_ledgers) => {
+            let vesting_key = DataKey::Vesting(user.clone(), badge_id);
+            let entry = VestingEntry {
+                total: badge.reward,
+                claimed: 0,
+                start_ledger: env.ledger().sequence(),
+                cliff_ledgers,
+                duration_ledgers,
+                token: badge.reward_token.clone(),
+            };
+            env.storage().persistent().set(&vesting_key, &entry);
+            bump(env, &vesting_key);
+        }
+        Payout::Atomic(token) => {
+            TokenClient::new(env, &token).transfer(
+                &env.current_contract_address(),
+                user,
+                &badge.reward,
+            );
+        }
+    }
+}
+
+/// Emit `BadgeAwarded` for an award already written by `store_award`.
+fn publish_awarded(
+    env: &Env,
+    user: &Address,
+    badge_id: u64,
+    reason: AwardReason,
+    badge: &BadgeDefinition,
+    note_hash: BytesN<32>,
+) {
+    BadgeAwarded {
+        user: user.clone(),
+        badge_id,
+        reason,
+        reward: badge.reward,
+        reward_token: awarded_reward_token(env, badge),
+        reward_token_decimals: reward_token_decimals(env),
+        note_hash,
+    }
+    .publish(env);
+}
+
+/// Remove and return the reward escrowed for `user`'s offer of `badge_id`.
+fn take_pending_reward(env: &Env, user: &Address, badge_id: u64) -> Option<i128> {
+    let key = DataKey::PendingReward(user.clone(), badge_id);
+    let amount = env.storage().persistent().get(&key)?;
+    env.storage().persistent().remove(&key);
+    Some(amount)
+}
+
+/// The per-user part of `check_invariants`: `user`'s reward balances are
+/// non-negative, pending offers are recorded, holdings have no duplicates,
+/// and vesting entries are within their totals.
+fn check_user_rewards(env: &Env, user: &Address) -> Result<(), Error> {
+    let storage = env.storage().persistent();
+    let claimable: i128 = storage.get(&DataKey::Claimable(user.clone())).unwrap_or(0);
+    if claimable < 0 {
+        return Err(Error::InvariantViolation);
+    }
+
+    let held = holdings(env, user);
+    for (i, badge_id) in held.iter().enumerate() {
+        if held.first_index_of(badge_id) != Some(i as u32) {
+            return Err(Error::InvariantViolation);
+        }
+        let vesting: Option<VestingEntry> = storage.get(&DataKey::Vesting(user.clone(), badge_id));
+        if vesting.is_some_and(|entry| entry.claimed < 0 || entry.claimed > entry.total) {
+            return Err(Error::InvariantViolation);
+        }
+        let token = storage
+            .get::<_, BadgeDefinition>(&DataKey::Badge(badge_id))
+            .and_then(|badge| badge.reward_token);
+        if let Some(token) = token {
+            let owed: i128 = storage
+                .get(&DataKey::ClaimableIn(user.clone(), token))
+                .unwrap_or(0);
+            if owed < 0 {
+                return Err(Error::InvariantViolation);
+            }
+        }
+    }
+
+    let pending: Vec<u64> = storage
+        .get(&DataKey::UserPending(user.clone()))
+        .unwrap_or_else(|| vec![env]);
+    for badge_id in pending.iter() {
+        if !storage.has(&DataKey::PendingBadge(user.clone(), badge_id)) {
+            return Err(Error::InvariantViolation);
+        }
+        let escrowed: i128 = storage
+            .get(&DataKey::PendingReward(user.clone(), badge_id))
+            .unwrap_or(0);
+        if escrowed < 0 {
+            return Err(Error::InvariantViolation);
+        }
+    }
+    Ok(())
+}
+
+/// Whether `user` holds every badge in `required`. Ignores privacy.
+fn holds_all(env: &Env, user: &Address, required: &Vec<u64>) -> bool {
+    let held = holdings(env, user);
+    required.iter().all(|id| held.contains(id))
+}
+
+/// Remove `badge_id` from `user`'s holdings along with its award record,
+/// ordinal, award source, vesting entry, and stack count. Returns `false` if the user did
+/// not hold it. Leaves the holder count to the caller.
+fn revoke(env: &Env, user: &Address, badge_id: u64) -> bool {
+    let user_key = DataKey::UserBadges(user.clone());
+    let Some(mut badges) = env.storage().persistent().get::<_, Vec<u64>>(&user_key) else {
+        return false;
+    };
+    let Some(pos) = badges.first_index_of(badge_id) else {
+        return false;
+    };
+
+    badges.remove(pos);
+    env.storage().persistent().set(&user_key, &badges);
+    bump(env, &user_key);
+    update_points_board(env, user, badges.len());
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AwardRecord(user.clone(), badge_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Vesting(user.clone(), badge_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AwardSource(user.clone(), badge_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AwardOrdinalOf(user.clone(), badge_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::BadgeStack(user.clone(), badge_id));
+    true
+}
+
+/// Move `user` to their place on the points leaderboard now that they have
+/// `points`. Among equal points the earlier arrival ranks first, and a full
+/// board drops its lowest entry to make room. Dropped users are forgotten,
+/// which is what makes the board approximate.
+fn update_points_board(env: &Env, user: &Address, points: u32) {
+    let key = DataKey::PointsLeaderboard;
+    let mut board: Vec<(Address, u32)> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| vec![env]);
+
+    let current = board.iter().position(|(holder, _)| &holder == user);
+    if let Some(pos) = current {
+        board.remove(pos as u32);
+    }
+    let pos = board
+        .iter()
+        .position(|(_, held)| held < points)
+        .map_or(board.len(), |pos| pos as u32);
+    let ranks = points > 0 && pos < MAX_PAGE_SIZE;
+    if current.is_none() && !ranks {
+        return;
+    }
+    if ranks {
+        board.insert(pos, (user.clone(), points));
+        if board.len() > MAX_PAGE_SIZE {
+            board.pop_back();
+        }
+    }
+
+    env.storage().persistent().set(&key, &board);
+    bump(env, &key);
+}
+
+/// Emit `RewardDue` for an award of `badge`, unless it carries no reward.
+fn publish_reward_due(env: &Env, user: &Address, badge_id: u64, badge: &BadgeDefinition) {
+    if badge.reward <= 0 {
+        return;
+    }
+    RewardDue {
+        user: user.clone(),
+        badge_id,
+        amount: badge.reward,
+        token: effective_reward_token(env, badge),
+    }
+    .publish(env);
+}
+
+/// Invoke `badge_id`'s subscriber, if set, and emit `SubscriberNotified`.
+///
+/// Uses the fallible client so a subscriber that errors or panics only has
+/// its own changes rolled back; the award itself stands.
+fn notify_subscriber(env: &Env, user: &Address, badge_id: u64) {
+    let subscriber: Option<Address> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::BadgeSubscriber(badge_id));
+    let Some(subscriber) = subscriber else {
+        return;
+    };
+    let result = BadgeSubscriberClient::new(env, &subscriber).try_on_badge_awarded(user, &badge_id);
+    SubscriberNotified {
+        user: user.clone(),
+        badge_id,
+        subscriber,
+        success: matches!(result, Ok(Ok(()))),
+    }
+    .publish(env);
+}
+
+/// The token `badge`'s reward is paid in: its override, else the default
+/// `RewardToken`, else `None`.
+fn effective_reward_token(env: &Env, badge: &BadgeDefinition) -> Option<Address> {
+    badge
+        .reward_token
+        .clone()
+        .or_else(|| env.storage().instance().get(&DataKey::RewardToken))
+}
+
+/// `BadgeAwarded::reward_token` for an award of `badge`: the effective reward
+/// token, or `NO_REWARD_TOKEN` if nothing will be paid in one.
+fn awarded_reward_token(env: &Env, badge: &BadgeDefinition) -> Address {
+    effective_reward_token(env, badge)
+        .filter(|_| badge.reward > 0)
+        .unwrap_or_else(|| no_reward_token(env))
+}
+
+fn no_reward_token(env: &Env) -> Address {
+    Address::from_str(env, NO_REWARD_TOKEN)
+}
+
+fn expected_reward_abi(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ExpectedRewardAbi)
+        .unwrap_or(0)
+}
+
+/// Check that `token`, the contract an atomic payout transfers through,
+/// reports `expected_reward_abi`, unless it is 0. A token that cannot answer
+/// counts as incompatible.
+fn require_compatible_reward(env: &Env, token: &Address) -> Result<(), Error> {
+    let expected = expected_reward_abi(env);
+    if expected == 0 {
+        return Ok(());
+    }
+    match RewardAbiClient::new(env, token).try_abi_version() {
+        Ok(Ok(version)) if version == expected => Ok(()),
+        _ => Err(Error::IncompatibleReward),
+    }
+}
+
+/// Require `user` to have joined `tournament_id` of the configured tournament
+/// contract.
+fn require_joined(env: &Env, user: &Address, tournament_id: u64) -> Result<(), Error> {
+    let tournaments: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::TournamentContract)
+        .ok_or(Error::TournamentContractNotSet)?;
+    if !TournamentViewClient::new(env, &tournaments).is_joined(&tournament_id, user) {
+        return Err(Error::PlayerNotJoined);
+    }
+    Ok(())
+}
+
+fn current_epoch(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::CurrentEpoch)
+        .unwrap_or(0)
+}
+
+/// Read the settlement mode, defaulting to `EventDriven`.
+fn settlement_mode(env: &Env) -> SettlementMode {
+    env.storage()
+        .instance()
+        .get(&DataKey::SettlementMode)
+        .unwrap_or(SettlementMode::EventDriven)
+}
+
+/// Read the batch event mode, defaulting to `PerItem`.
+fn batch_event_mode(env: &Env) -> BatchEventMode {
+    env.storage()
+        .instance()
+        .get(&DataKey::BatchEventMode)
+        .unwrap_or(BatchEventMode::PerItem)
+}
+
+/// The all-zero note hash used when an award carries no citation.
+fn no_note(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[0u8; 32])
+}
+
+/// Count one award against `issuer`.
+fn record_issuer_award(env: &Env, issuer: &Address) {
+    let key = DataKey::IssuerAwardCount(issuer.clone());
+    let count: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&key, &count.saturating_add(1));
+    bump(env, &key);
+}
+
+/// Number of users currently holding `badge_id`.
+fn holder_count(env: &Env, badge_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HolderCount(badge_id))
+        .unwrap_or(0)
+}
+
+/// Number of pending offers of `badge_id`.
+fn offer_count(env: &Env, badge_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OfferCount(badge_id))
+        .unwrap_or(0)
+}
+
+/// Move `badge_id`'s pending-offer count by `delta`, dropping the entry at
+/// zero.
+fn add_offer_count(env: &Env, badge_id: u64, delta: i32) {
+    let key = DataKey::OfferCount(badge_id);
+    let count = offer_count(env, badge_id).saturating_add_signed(delta);
+    if count == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &count);
+        bump(env, &key);
+    }
+}
+
+/// Ledgers left before `user` may earn another badge in `family`; 0 if none.
+fn cooldown_remaining(env: &Env, user: &Address, family: u32) -> u32 {
+    let Some((awarded_at, cooldown)) = env
+        .storage()
+        .persistent()
+        .get::<_, (u32, u32)>(&DataKey::FamilyAward(user.clone(), family))
+    else {
+        return 0;
+    };
+    awarded_at
+        .saturating_add(cooldown)
+        .saturating_sub(env.ledger().sequence())
+}
+
+/// Hash two merkle nodes in ascending byte order.
+fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let mut preimage = Bytes::from_array(env, &lo.to_array());
+    preimage.extend_from_array(&hi.to_array());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Append `badge_id` to the reverse index for `criteria_hash`.
+fn index_badge_hash(env: &Env, criteria_hash: &BytesN<32>, badge_id: u64) {
+    let key = DataKey::HashToBadges(criteria_hash.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| vec![env]);
+    ids.push_back(badge_id);
+    env.storage().persistent().set(&key, &ids);
+    bump(env, &key);
+}
+
+/// Append `badge_id` to the index of all defined badges.
+fn index_badge(env: &Env, badge_id: u64) {
+    let key = DataKey::BadgeIndex;
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| vec![env]);
+    ids.push_back(badge_id);
+    env.storage().persistent().set(&key, &ids);
+    bump(env, &key);
+}
+
+/// Read a `storage_stats` counter.
+fn stat(env: &Env, key: &DataKey) -> u64 {
+    env.storage().instance().get(key).unwrap_or(0)
+}
+
+/// Move a `storage_stats` counter by `delta`, saturating at the bounds.
+fn add_stat(env: &Env, key: DataKey, delta: i64) {
+    let value = stat(env, &key).saturating_add_signed(delta);
+    env.storage().instance().set(&key, &value);
+}
+
+/// `user`'s real holdings, ignoring `set_private`.
+fn holdings(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UserBadges(user.clone()))
+        .unwrap_or_else(|| vec![env])
+}
+
+/// `user`'s holdings as public getters report them: empty for a private
+/// user. Never use this for eligibility or award checks.
+fn visible_holdings(env: &Env, user: &Address) -> Vec<u64> {
+    if is_private(env, user) {
+        return vec![env];
+    }
+    holdings(env, user)
+}
+
+fn is_private(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Private(user.clone()))
+}
+
+/// Remove `badge_id` from the index stored under `key`, dropping the entry
+/// once it is empty.
+fn unindex(env: &Env, key: DataKey, badge_id: u64) {
+    let Some(mut ids) = env.storage().persistent().get::<_, Vec<u64>>(&key) else {
+        return;
+    };
+    let Some(pos) = ids.first_index_of(badge_id) else {
+        return;
+    };
+    ids.remove(pos);
+    if ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &ids);
+        bump(env, &key);
+    }
+}
+
+/// Stored layout version. Deployments from before versioning are on 1.
+fn schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+/// Read the per-user badge cap, defaulting to 0 (unlimited).
+fn max_badges_per_user(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MaxBadgesPerUser)
+        .unwrap_or(0)
+}
+
+/// Read the event level, defaulting to `Standard`.
+fn event_level(env: &Env) -> EventLevel {
+    env.storage()
+        .instance()
+        .get(&DataKey::EventLevel)
+        .unwrap_or(EventLevel::Standard)
+}
+
+/// Whether events of `level` fire at the configured event level.
+fn emits(env: &Env, level: EventLevel) -> bool {
+    event_level(env) >= level
+}
+
+/// Extend the TTL of persistent `key` and emit `StorageBumped` at the
+/// `Verbose` event level, or at `Standard` with verbose storage events on.
+fn bump(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+    let verbose = match event_level(env) {
+        EventLevel::Minimal => false,
+        EventLevel::Standard => env
+            .storage()
+            .instance()
+            .get(&DataKey::VerboseStorageEvents)
+            .unwrap_or(false),
+        EventLevel::Verbose => true,
+    };
+    if verbose {
+        StorageBumped {
+            key_kind: key_kind(key),
+            new_ttl: PERSISTENT_BUMP_LEDGERS,
+        }
+        .publish(env);
+    }
+}
+
+fn is_paused(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Paused)
+}
+
+fn require_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Stable numeric tag for a persistent `DataKey` variant, used in
+/// `StorageBumped`. Instance keys are never bumped and map to 0.
+fn key_kind(key: &DataKey) -> u32 {
+    match key {
+        DataKey::Admin
+        | DataKey::RewardContract
+        | DataKey::MaxBadgesPerUser
+        | DataKey::AdminRenounced
+        | DataKey::RewardTokenDecimals
+        | DataKey::VerboseStorageEvents
+        | DataKey::SchemaVersion
+        | DataKey::RewardToken
+        | DataKey::BatchEventMode
+        | DataKey::SettlementMode
+        | DataKey::ContractName
+        | DataKey::AwardOrdinal
+        | DataKey::CurrentEpoch
+        | DataKey::ExpectedRewardAbi
+        | DataKey::TournamentContract
+        | DataKey::EventLevel
+        | DataKey::Paused
+        | DataKey::BadgeCount
+        | DataKey::MetaBadgeCount
+        | DataKey::HoldingCount => 0,
+        DataKey::Badge(_) => 1,
+        DataKey::UserBadges(_) => 2,
+        DataKey::BadgeMeta(_) => 3,
+        DataKey::HashToBadges(_) => 4,
+        DataKey::Evaluated(_, _) => 5,
+        DataKey::MetaBadge(_) => 6,
+        DataKey::ClaimRoot(_) => 7,
+        DataKey::BadgeIndex => 8,
+        DataKey::PendingBadge(_, _) => 9,
+        DataKey::FamilyAward(_, _) => 10,
+        DataKey::HolderCount(_) => 11,
+        DataKey::IssuerAwardCount(_) => 12,
+        DataKey::AwardRecord(_, _) => 13,
+        DataKey::Claimable(_) => 14,
+        DataKey::Badge64(_) => 15,
+        DataKey::ClaimableIn(_, _) => 16,
+        DataKey::BadgeVesting(_) => 17,
+        DataKey::Vesting(_, _) => 18,
+        DataKey::LastActive(_) => 19,
+        DataKey::AwardSource(_, _) => 20,
+        DataKey::AwardOrdinalOf(_, _) => 21,
+        DataKey::Private(_) => 22,
+        DataKey::PendingReward(_, _) => 23,
+        DataKey::UserPending(_) => 24,
+        DataKey::PointsLeaderboard => 25,
+        DataKey::BadgeStack(_, _) => 26,
+        DataKey::BadgeSubscriber(_) => 27,
+        DataKey::OfferCount(_) => 28,
+    }
+}
+
+/// Read the configured reward token decimals, defaulting to 0.
+fn reward_token_decimals(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::RewardTokenDecimals)
+        .unwrap_or(0)
+}
+
+/// Fetch the badge definition or return `BadgeNotFound`.
+fn require_badge_exists(env: &Env, badge_id: u64) -> Result<BadgeDefinition, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Badge(badge_id))
+        .ok_or(Error::BadgeNotFound)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{Address as _, Events as _, Ledger as _},
+        xdr::ToXdr,
+        Address, Bytes, BytesN, Env, Event as _,
+    };
+
+    // ------------------------------------------------------------------
+    // Test helpers
+    // ------------------------------------------------------------------
+
+    fn make_hash(env: &Env, seed: u8) -> BytesN<32> {
+        BytesN::from_array(env, &[seed; 32])
+    }
+
+    fn setup(env: &Env) -> (AchievementBadgeClient<'_>, Address, Address) {
+        let admin = Address::generate(env);
+        let reward_contract = Address::generate(env);
+
+        let contract_id = env.register(AchievementBadge, ());
+        let client = AchievementBadgeClient::new(env, &contract_id);
+
+        env.mock_all_auths();
+        client.init(
+            &admin,
+            &reward_contract,
+            &7u32,
+            &String::from_str(env, "Badges"),
+            &0u32,
+        );
+
+        (client, admin, reward_contract)
+    }
+
+    // ------------------------------------------------------------------
+    // 1. init
+    // ------------------------------------------------------------------
+
+    #[cfg(feature = "test-bypass-auth")]
+    #[test]
+    fn bypass_auth_skips_auth_and_admin_checks() {
+        let env = Env::default();
+        let client = AchievementBadgeClient::new(&env, &env.register(AchievementBadge, ()));
+        // No `mock_all_auths`: nobody signs anything.
+        let (admin, stranger, user) = (
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        );
+        client.init(
+            &admin,
+            &Address::generate(&env),
+            &7u32,
+            &String::from_str(&env, "Badges"),
+            &0u32,
+        );
+        client.define_badge(&stranger, &1u64, &make_hash(&env, 1), &0i128);
+        client.award_badge(&stranger, &user, &1u64);
+        client.set_private(&user, &true);
+        assert_eq!(
+            client.badges_of_as(&user, &user),
+            soroban_sdk::vec![&env, 1u64]
+        );
+    }
+
+    #[test]
+    fn test_init_stores_reward_token_decimals() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        assert_eq!(client.reward_token_decimals(), 7);
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 100), &5i128);
+        assert_eq!(
+            env.events().all(),
+            [BadgeDefined {
+                badge_id: 1,
+                criteria_hash: make_hash(&env, 100),
+                reward: 5,
+                reward_token_decimals: 7,
+                name: None,
+            }
+            .to_xdr(&env, &client.address)]
+        );
+    }
+
+    #[test]
+    fn test_init_rejects_excessive_decimals() {
+        let env = Env::default();
+        let contract_id = env.register(AchievementBadge, ());
+        let client = AchievementBadgeClient::new(&env, &contract_id);
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let reward_contract = Address::generate(&env);
+        let name = String::from_str(&env, "Badges");
+        assert_eq!(
+            client.try_init(&admin, &reward_contract, &19u32, &name, &0u32),
+            Err(Ok(Error::InvalidInput))
+        );
+        client.init(&admin, &reward_contract, &18u32, &name, &0u32);
+        assert_eq!(client.reward_token_decimals(), 18);
+    }
+
+    #[test]
+    fn test_init_stores_contract_name() {
+        let env = Env::default();
+        let contract_id = env.register(AchievementBadge, ());
+        let client = AchievementBadgeClient::new(&env, &contract_id);
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let reward_contract = Address::generate(&env);
+        assert!(!client.is_initialized());
+        assert_eq!(client.contract_name(), String::from_str(&env, ""));
+        for bad in ["", "this name is longer than 32 bytes"] {
+            assert_eq!(
+                client.try_init(
+                    &admin,
+                    &reward_contract,
+                    &7u32,
+                    &String::from_str(&env, bad),
+                    &0u32
+                ),
+                Err(Ok(Error::InvalidInput))
+            );
+        }
+
+        let name = String::from_str(&env, "Season 3 badges - mainnet east");
+        client.init(&admin, &reward_contract, &7u32, &name, &0u32);
+        assert_eq!(client.contract_name(), name);
+        assert!(client.is_initialized());
+
+        client.renounce_admin(&admin);
+        assert!(client.is_initialized());
+    }
+
+    #[test]
+    fn test_init_rejects_reinit() {
+        let env = Env::default();
+        let (client, admin, reward_contract) = setup(&env);
+        env.mock_all_auths();
+
+        let result = client.try_init(
+            &admin,
+            &reward_contract,
+            &7u32,
+            &String::from_str(&env, "Badges"),
+            &0u32,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_uninit_privileged_calls_rejected() {
+        let env = Env::default();
+        let contract_id = env.register(AchievementBadge, ());
+        let client = AchievementBadgeClient::new(&env, &contract_id);
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let hash = make_hash(&env, 1);
+
+        assert!(client
+            .try_define_badge(&admin, &1u64, &hash, &0i128)
+            .is_err());
+        assert!(client.try_evaluate_user(&admin, &user, &1u64).is_err());
+        assert!(client.try_award_badge(&admin, &user, &1u64).is_err());
+    }
+
+    #[test]
+    fn test_renounce_admin_locks_admin_paths() {
+        let env = Env::default();
+        let (client, admin, reward_contract) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 50), &0i128);
+        let holder = Address::generate(&env);
+        client.award_badge(&admin, &holder, &1u64);
+
+        client.renounce_admin(&admin);
+
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_define_badge(&admin, &2u64, &make_hash(&env, 51), &0i128),
+            Err(Ok(Error::AdminRenounced))
+        );
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::AdminRenounced))
+        );
+        assert_eq!(
+            client.try_set_max_badges_per_user(&admin, &1u32),
+            Err(Ok(Error::AdminRenounced))
+        );
+        assert_eq!(
+            client.try_renounce_admin(&admin),
+            Err(Ok(Error::AdminRenounced))
+        );
+        assert_eq!(
+            client.try_init(
+                &user,
+                &reward_contract,
+                &7u32,
+                &String::from_str(&env, "Badges"),
+                &0u32
+            ),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+
+        // Read paths are unaffected.
+        assert_eq!(client.badges_of(&holder).len(), 1);
+        assert!(client.get_badge_summary(&1u64).found);
+    }
+
+    #[test]
+    fn test_renounce_admin_non_admin_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let non_admin = Address::generate(&env);
+        assert_eq!(
+            client.try_renounce_admin(&non_admin),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 52), &0i128);
+    }
+
+    // ------------------------------------------------------------------
+    // 2. define_badge
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_define_badge_success() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 42);
+        client.define_badge(&admin, &1u64, &hash, &500i128);
+        // No panic = success
+    }
+
+    #[test]
+    fn test_define_badge_duplicate_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 1);
+        client.define_badge(&admin, &10u64, &hash, &0i128);
+
+        let result = client.try_define_badge(&admin, &10u64, &hash, &0i128);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_define_badge_negative_reward_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 2);
+        let result = client.try_define_badge(&admin, &1u64, &hash, &-1i128);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_define_badge_non_admin_rejected() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let non_admin = Address::generate(&env);
+        let hash = make_hash(&env, 3);
+        let result = client.try_define_badge(&non_admin, &1u64, &hash, &0i128);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_delete_badge_definition_allows_redefinition() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 4);
+        client.define_badge(&admin, &1u64, &hash, &10i128);
+        client.define_badge(&admin, &2u64, &hash, &0i128);
+
+        client.delete_badge_definition(&admin, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [BadgeDefinitionDeleted { badge_id: 1 }.to_xdr(&env, &client.address)]
+        );
+        assert_eq!(client.badges_by_hash(&hash), soroban_sdk::vec![&env, 2u64]);
+        assert_eq!(
+            client.try_award_badge(&admin, &Address::generate(&env), &1u64),
+            Err(Ok(Error::BadgeNotFound))
+        );
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 5), &0i128);
+        assert_eq!(
+            client.badges_by_hash(&make_hash(&env, 5)),
+            soroban_sdk::vec![&env, 1u64]
+        );
+    }
+
+    #[test]
+    fn test_delete_badge_definition_rejects_held_badges() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 6), &0i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(
+            client.try_delete_badge_definition(&admin, &1u64),
+            Err(Ok(Error::BadgeLocked))
+        );
+        assert_eq!(
+            client.try_delete_badge_definition(&admin, &9u64),
+            Err(Ok(Error::BadgeNotFound))
+        );
+        assert_eq!(
+            client.try_delete_badge_definition(&Address::generate(&env), &1u64),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        // Once the last holder is revoked the ID is free again.
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, user], &1u64);
+        client.delete_badge_definition(&admin, &1u64);
+
+        // Pending offers lock the definition until accepted or declined.
+        client.define_badge(&admin, &2u64, &make_hash(&env, 7), &25i128);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        client.offer_badge(&admin, &alice, &2u64);
+        client.offer_badge(&admin, &bob, &2u64);
+        client.offer_badge(&admin, &bob, &2u64);
+        assert_eq!(
+            client.try_delete_badge_definition(&admin, &2u64),
+            Err(Ok(Error::BadgeLocked))
+        );
+        client.decline_badge(&alice, &2u64);
+        assert_eq!(
+            client.try_delete_badge_definition(&admin, &2u64),
+            Err(Ok(Error::BadgeLocked))
+        );
+        client.accept_badge(&bob, &2u64);
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, bob], &2u64);
+        client.delete_badge_definition(&admin, &2u64);
+    }
+
+    #[test]
+    fn test_badges_by_hash_indexes_shared_criteria() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let shared = make_hash(&env, 40);
+        let unique = make_hash(&env, 41);
+        client.define_badge(&admin, &1u64, &shared, &0i128);
+        client.define_badge(&admin, &2u64, &unique, &0i128);
+        client.define_badge(&admin, &3u64, &shared, &0i128);
+
+        let ids = client.badges_by_hash(&shared);
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids.get(0).unwrap(), 1u64);
+        assert_eq!(ids.get(1).unwrap(), 3u64);
+        assert_eq!(client.badges_by_hash(&unique).len(), 1);
+        assert_eq!(client.badges_by_hash(&make_hash(&env, 99)).len(), 0);
+    }
+
+    #[test]
+    fn test_badges_by_hash_ignores_rejected_definition() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 42);
+        client.define_badge(&admin, &1u64, &hash, &0i128);
+        assert!(client
+            .try_define_badge(&admin, &1u64, &hash, &0i128)
+            .is_err());
+
+        assert_eq!(client.badges_by_hash(&hash).len(), 1);
+    }
+
+    #[test]
+    fn test_verify_criteria_sha256() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let doc = Bytes::from_slice(&env, b"win 10 ranked matches");
+        let hash: BytesN<32> = env.crypto().sha256(&doc).into();
+        client.define_badge(&admin, &1u64, &hash, &0i128);
+
+        assert!(client.verify_criteria(&1u64, &doc));
+        assert!(!client.verify_criteria(&1u64, &Bytes::from_slice(&env, b"win 1 match")));
+    }
+
+    #[test]
+    fn test_verify_criteria_keccak256() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let doc = Bytes::from_slice(&env, b"win 10 ranked matches");
+        let hash: BytesN<32> = env.crypto().keccak256(&doc).into();
+        let definition = BadgeDefinition {
+            hash_algo: HashAlgo::Keccak256,
+            ..BadgeDefinition::new(hash, 0)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+
+        assert!(client.verify_criteria(&1u64, &doc));
+
+        // A SHA-256 commitment declared as Keccak-256 must not verify.
+        let sha_hash: BytesN<32> = env.crypto().sha256(&doc).into();
+        let mislabeled = BadgeDefinition {
+            hash_algo: HashAlgo::Keccak256,
+            ..BadgeDefinition::new(sha_hash, 0)
+        };
+        client.define_badge_full(&admin, &2u64, &mislabeled);
+        assert!(!client.verify_criteria(&2u64, &doc));
+    }
+
+    #[test]
+    fn test_verify_criteria_unknown_badge() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+
+        let doc = Bytes::from_slice(&env, b"anything");
+        assert_eq!(
+            client.try_verify_criteria(&7u64, &doc),
+            Err(Ok(Error::BadgeNotFound))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 3. evaluate_user
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_evaluate_user_success() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 5);
+        client.define_badge(&admin, &1u64, &hash, &0i128);
+
+        let user = Address::generate(&env);
+        client.evaluate_user(&admin, &user, &1u64);
+        // No panic = success
+    }
+
+    #[test]
+    fn test_evaluate_user_records_history() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 15), &0i128);
+        let user = Address::generate(&env);
+        assert!(!client.was_evaluated(&user, &1u64));
+        assert_eq!(client.evaluated_at(&user, &1u64), None);
+
+        env.ledger().set_sequence_number(120);
+        client.evaluate_user(&admin, &user, &1u64);
+
+        assert!(client.was_evaluated(&user, &1u64));
+        assert_eq!(client.evaluated_at(&user, &1u64), Some(120));
+    }
+
+    #[test]
+    fn test_award_requires_evaluation_when_configured() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            require_evaluation: true,
+            ..BadgeDefinition::new(make_hash(&env, 16), 0)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::NotEvaluated))
+        );
+
+        client.evaluate_user(&admin, &user, &1u64);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.badges_of(&user).len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_user_undefined_badge_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        // badge 999 not defined
+        let result = client.try_evaluate_user(&admin, &user, &999u64);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_evaluate_user_non_admin_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 6);
+        client.define_badge(&admin, &1u64, &hash, &0i128);
+
+        let non_admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let result = client.try_evaluate_user(&non_admin, &user, &1u64);
+        assert!(result.is_err());
+    }
+
+    // ------------------------------------------------------------------
+    // 4. award_badge
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_award_badge_success() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 7);
+        client.define_badge(&admin, &1u64, &hash, &100i128);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+
+        let badges = client.badges_of(&user);
+        assert_eq!(badges.len(), 1);
+        assert_eq!(badges.get(0).unwrap(), 1u64);
+    }
+
+    #[test]
+    fn test_award_badge_enforces_award_window() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            award_window: AwardWindow::Between(100, 200),
+            ..BadgeDefinition::new(make_hash(&env, 17), 0)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+
+        let early = Address::generate(&env);
+        let on_time = Address::generate(&env);
+        let late = Address::generate(&env);
+
+        env.ledger().set_sequence_number(99);
+        assert_eq!(
+            client.try_award_badge(&admin, &early, &1u64),
+            Err(Ok(Error::AwardWindowClosed))
+        );
+
+        env.ledger().set_sequence_number(200);
+        client.award_badge(&admin, &on_time, &1u64);
+
+        env.ledger().set_sequence_number(201);
+        assert_eq!(
+            client.try_award_badge(&admin, &late, &1u64),
+            Err(Ok(Error::AwardWindowClosed))
+        );
+    }
+
+    #[test]
+    fn test_define_badge_rejects_inverted_window() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            award_window: AwardWindow::Between(200, 200),
+            ..BadgeDefinition::new(make_hash(&env, 18), 0)
+        };
+        assert_eq!(
+            client.try_define_badge_full(&admin, &1u64, &definition),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
+
+    #[test]
+    fn test_award_badge_undefined_badge_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        let result = client.try_award_badge(&admin, &user, &999u64);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_award_badge_duplicate_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 8);
+        client.define_badge(&admin, &1u64, &hash, &0i128);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+
+        let result = client.try_award_badge(&admin, &user, &1u64);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_award_badge_non_admin_rejected() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 9);
+        client.define_badge(&admin, &2u64, &hash, &0i128);
+
+        let non_admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let result = client.try_award_badge(&non_admin, &user, &2u64);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_award_multiple_badges_to_same_user() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+
+        for id in 1u64..=3 {
+            let hash = make_hash(&env, id as u8);
+            client.define_badge(&admin, &id, &hash, &0i128);
+            client.award_badge(&admin, &user, &id);
+        }
+
+        let badges = client.badges_of(&user);
+        assert_eq!(badges.len(), 3);
+        assert_eq!(badges.get(0).unwrap(), 1u64);
+        assert_eq!(badges.get(1).unwrap(), 2u64);
+        assert_eq!(badges.get(2).unwrap(), 3u64);
+    }
+
+    #[test]
+    fn test_same_badge_different_users() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 10);
+        client.define_badge(&admin, &1u64, &hash, &50i128);
+
+        let user_a = Address::generate(&env);
+        let user_b = Address::generate(&env);
+
+        client.award_badge(&admin, &user_a, &1u64);
+        client.award_badge(&admin, &user_b, &1u64);
+
+        assert_eq!(client.badges_of(&user_a).len(), 1);
+        assert_eq!(client.badges_of(&user_b).len(), 1);
+    }
+
+    #[test]
+    fn test_award_badge_defaults_to_manual_reason() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 13), &25i128);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+
+        let expected = BadgeAwarded {
+            user: user.clone(),
+            badge_id: 1,
+            reason: AwardReason::Manual,
+            reward: 25,
+            reward_token: no_reward_token(&env),
+            reward_token_decimals: 7,
+            note_hash: BytesN::from_array(&env, &[0u8; 32]),
+        };
+        let due = RewardDue {
+            user,
+            badge_id: 1,
+            amount: 25,
+            token: None,
+        };
+        assert_eq!(
+            env.events().all(),
+            [
+                expected.to_xdr(&env, &client.address),
+                due.to_xdr(&env, &client.address)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_award_badge_with_reason_emits_reason_topic() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 14), &0i128);
+
+        let user = Address::generate(&env);
+        client.award_badge_with_reason(&admin, &user, &1u64, &AwardReason::Migration);
+
+        let expected = BadgeAwarded {
+            user: user.clone(),
+            badge_id: 1,
+            reason: AwardReason::Migration,
+            reward: 0,
+            reward_token: no_reward_token(&env),
+            reward_token_decimals: 7,
+            note_hash: BytesN::from_array(&env, &[0u8; 32]),
+        };
+        assert_eq!(env.events().all(), [expected.to_xdr(&env, &client.address)]);
+        assert_eq!(client.badges_of(&user).len(), 1);
+
+        // The duplicate guard applies regardless of reason.
+        let result =
+            client.try_award_badge_with_reason(&admin, &user, &1u64, &AwardReason::Automated);
+        assert_eq!(result, Err(Ok(Error::BadgeAlreadyAwarded)));
+    }
+
+    #[test]
+    fn test_award_badge_batch_summary_mode_emits_one_event() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 15), &0i128);
+        assert_eq!(client.batch_event_mode(), BatchEventMode::PerItem);
+        client.set_batch_event_mode(&admin, &BatchEventMode::Summary);
+
+        let users = vec![
+            &env,
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ];
+        assert_eq!(client.award_badge_batch(&admin, &users, &1u64), 3);
+
+        let expected = BatchAwarded {
+            badge_id: 1,
+            count: 3,
+            first_user: users.get(0).unwrap(),
+            last_user: users.get(2).unwrap(),
+        };
+        assert_eq!(env.events().all(), [expected.to_xdr(&env, &client.address)]);
+        for user in users.iter() {
+            assert_eq!(client.badges_of(&user), vec![&env, 1u64]);
+        }
+        assert_eq!(client.holder_count(&1u64), 3);
+        assert_eq!(client.issuer_stats(&admin), 3);
+    }
+
+    #[test]
+    fn test_award_badge_batch_summary_mode_still_emits_reward_due() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 18), &4i128);
+        client.set_batch_event_mode(&admin, &BatchEventMode::Summary);
+
+        let users = vec![&env, Address::generate(&env), Address::generate(&env)];
+        client.award_badge_batch(&admin, &users, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                RewardDue {
+                    user: users.get(0).unwrap(),
+                    badge_id: 1,
+                    amount: 4,
+                    token: None
+                }
+                .to_xdr(&env, &client.address),
+                RewardDue {
+                    user: users.get(1).unwrap(),
+                    badge_id: 1,
+                    amount: 4,
+                    token: None
+                }
+                .to_xdr(&env, &client.address),
+                BatchAwarded {
+                    badge_id: 1,
+                    count: 2,
+                    first_user: users.get(0).unwrap(),
+                    last_user: users.get(1).unwrap(),
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_award_badge_with_source_records_tournament() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 21), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 22), &0i128);
+        let user = Address::generate(&env);
+
+        client.award_badge_with_source(&admin, &user, &1u64, &42u64);
+        assert_eq!(
+            env.events().all(),
+            [BadgeAwarded {
+                user: user.clone(),
+                badge_id: 1,
+                reason: AwardReason::Automated,
+                reward: 0,
+                reward_token: no_reward_token(&env),
+                reward_token_decimals: 7,
+                note_hash: BytesN::from_array(&env, &[0u8; 32]),
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert_eq!(client.award_source(&user, &1u64), 42);
+
+        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(client.award_source(&user, &2u64), 0);
+
+        client.revoke_badge_batch(&admin, &vec![&env, user.clone()], &1u64);
+        assert_eq!(client.award_source(&user, &1u64), 0);
+    }
+
+    #[test]
+    fn test_award_ordinal_orders_awards_within_a_ledger() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 23), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 24), &0i128);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+        client.award_badge(&admin, &alice, &1u64);
+        client.award_badge(&admin, &bob, &1u64);
+        client.award_badge(&admin, &alice, &2u64);
+        assert_eq!(client.award_ordinal(&alice, &1u64), Some(1));
+        assert_eq!(client.award_ordinal(&bob, &1u64), Some(2));
+        assert_eq!(client.award_ordinal(&alice, &2u64), Some(3));
+        assert_eq!(client.award_ordinal(&bob, &2u64), None);
+
+        // Revoked awards drop their ordinal; a re-award takes a fresh one.
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, alice.clone()], &1u64);
+        assert_eq!(client.award_ordinal(&alice, &1u64), None);
+        client.award_badge(&admin, &alice, &1u64);
+        assert_eq!(client.award_ordinal(&alice, &1u64), Some(4));
+    }
+
+    #[test]
+    fn test_award_badge_in_epoch_rejects_closed_epochs() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 25), &0i128);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        assert_eq!(client.current_epoch(), 0);
+        client.award_badge_in_epoch(&admin, &alice, &1u64, &0u32);
+
+        assert_eq!(client.advance_epoch(&admin), 1);
+        assert_eq!(
+            env.events().all(),
+            [EpochAdvanced { epoch: 1 }.to_xdr(&env, &client.address)]
+        );
+        assert!(client.is_epoch_closed(&0u32));
+        assert!(!client.is_epoch_closed(&1u32));
+
+        assert_eq!(
+            client.try_award_badge_in_epoch(&admin, &bob, &1u64, &0u32),
+            Err(Ok(Error::EpochClosed))
+        );
+        assert_eq!(
+            client.try_award_badge_in_epoch(&admin, &bob, &1u64, &2u32),
+            Err(Ok(Error::InvalidInput))
+        );
+        client.award_badge_in_epoch(&admin, &bob, &1u64, &1u32);
+        assert_eq!(client.badges_of(&bob), soroban_sdk::vec![&env, 1u64]);
+        assert_eq!(
+            client.try_advance_epoch(&Address::generate(&env)),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
+
+    #[test]
+    fn test_revoke_badge_batch_skips_non_holders() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 19), &0i128);
+        let (a, b, c) = (
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        );
+        client.award_badge_batch(&admin, &vec![&env, a.clone(), c.clone()], &1u64);
+
+        let revoked =
+            client.revoke_badge_batch(&admin, &vec![&env, a.clone(), b.clone(), c.clone()], &1u64);
+        assert_eq!(revoked, vec![&env, a.clone(), c.clone()]);
+        assert_eq!(
+            env.events().all(),
+            [
+                BadgeRevoked {
+                    user: a.clone(),
+                    badge_id: 1
+                }
+                .to_xdr(&env, &client.address),
+                BadgeRevoked {
+                    user: c.clone(),
+                    badge_id: 1
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+        assert_eq!(client.holder_count(&1u64), 0);
+        assert_eq!(
+            client.holds_badge_status(&a, &1u64),
+            HoldStatus::DoesNotHold
+        );
+        assert_eq!(client.badges_of_detailed(&a).len(), 0);
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+
+        // A revoked user can earn the badge again.
+        client.award_badge(&admin, &a, &1u64);
+        assert_eq!(client.holder_count(&1u64), 1);
+    }
+
+    #[test]
+    fn test_revoke_badge_batch_validation_and_vesting() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 20), &50i128);
+        client.set_badge_vesting(&admin, &1u64, &0u32, &100u32);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert!(client.vesting_of(&user, &1u64).is_some());
+
+        assert_eq!(
+            client.try_revoke_badge_batch(&admin, &vec![&env], &1u64),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(
+            client.try_revoke_badge_batch(&admin, &vec![&env, user.clone()], &2u64),
+            Err(Ok(Error::BadgeNotFound))
+        );
+        assert_eq!(
+            client.try_revoke_badge_batch(&user, &vec![&env, user.clone()], &1u64),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        client.revoke_badge_batch(&admin, &vec![&env, user.clone()], &1u64);
+        assert_eq!(client.vesting_of(&user, &1u64), None);
+    }
+
+    #[test]
+    fn test_award_badge_batch_per_item_mode_emits_per_award() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 16), &0i128);
+        let users = vec![&env, Address::generate(&env), Address::generate(&env)];
+        client.award_badge_batch(&admin, &users, &1u64);
+        assert_eq!(env.events().all().events().len(), 2);
+
+        client.define_badge(&admin, &2u64, &make_hash(&env, 17), &0i128);
+        client.set_batch_event_mode(&admin, &BatchEventMode::Both);
+        client.award_badge_batch(&admin, &users, &2u64);
+        assert_eq!(env.events().all().events().len(), 3);
+    }
+
+    #[test]
+    fn test_award_badge_batch_is_atomic() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 18), &0i128);
+        let holder = Address::generate(&env);
+        client.award_badge(&admin, &holder, &1u64);
+
+        let fresh = Address::generate(&env);
+        let users = vec![&env, fresh.clone(), holder];
+        let result = client.try_award_badge_batch(&admin, &users, &1u64);
+        assert_eq!(result, Err(Ok(Error::BadgeAlreadyAwarded)));
+        assert_eq!(client.badges_of(&fresh).len(), 0);
+
+        let empty: Vec<Address> = vec![&env];
+        let result = client.try_award_badge_batch(&admin, &empty, &1u64);
+        assert_eq!(result, Err(Ok(Error::InvalidInput)));
+    }
+
+    #[test]
+    fn test_award_badge_respects_user_cap() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        assert_eq!(client.max_badges_per_user(), 0);
+        client.set_max_badges_per_user(&admin, &2u32);
+        assert_eq!(client.max_badges_per_user(), 2);
+
+        let user = Address::generate(&env);
+        for id in 1u64..=3 {
+            client.define_badge(&admin, &id, &make_hash(&env, id as u8), &0i128);
+        }
+        client.award_badge(&admin, &user, &1u64);
+        client.award_badge(&admin, &user, &2u64);
+
+        let result = client.try_award_badge(&admin, &user, &3u64);
+        assert_eq!(result, Err(Ok(Error::UserBadgeLimit)));
+        assert_eq!(client.badges_of(&user).len(), 2);
+
+        // Other users are unaffected, and 0 lifts the cap.
+        let other = Address::generate(&env);
+        client.award_badge(&admin, &other, &3u64);
+        client.set_max_badges_per_user(&admin, &0u32);
+        client.award_badge(&admin, &user, &3u64);
+        assert_eq!(client.badges_of(&user).len(), 3);
+    }
+
+    #[test]
+    fn test_set_max_badges_per_user_non_admin_rejected() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+        env.mock_all_auths();
+
+        let non_admin = Address::generate(&env);
+        let result = client.try_set_max_badges_per_user(&non_admin, &5u32);
+        assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    }
+
+    // ------------------------------------------------------------------
+    // Award notes and records
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_award_badge_with_note_records_citation() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 160), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 161), &0i128);
+
+        let user = Address::generate(&env);
+        let note = make_hash(&env, 42);
+        env.ledger().set_sequence_number(300);
+        client.award_badge_with_note(&admin, &user, &1u64, &AwardReason::Automated, &note);
+        assert_eq!(
+            env.events().all(),
+            [BadgeAwarded {
+                user: user.clone(),
+                badge_id: 1,
+                reason: AwardReason::Automated,
+                reward: 0,
+                reward_token: no_reward_token(&env),
+                reward_token_decimals: 7,
+                note_hash: note.clone(),
+            }
+            .to_xdr(&env, &client.address)]
+        );
+
+        env.ledger().set_sequence_number(310);
+        client.award_badge(&admin, &user, &2u64);
+
+        assert_eq!(
+            client.badges_of_detailed(&user),
+            soroban_sdk::vec![
+                &env,
+                AwardRecord {
+                    badge_id: 1,
+                    awarded_ledger: 300,
+                    reason: AwardReason::Automated,
+                    note_hash: note
+                },
+                AwardRecord {
+                    badge_id: 2,
+                    awarded_ledger: 310,
+                    reason: AwardReason::Manual,
+                    note_hash: BytesN::from_array(&env, &[0u8; 32]),
+                },
+            ]
+        );
+        assert_eq!(client.badges_of_detailed(&Address::generate(&env)).len(), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // Issuer stats
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_issuer_stats_counts_admin_awards() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 150), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 151), &0i128);
+        assert_eq!(client.issuer_stats(&admin), 0);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        client.award_badge_with_reason(
+            &admin,
+            &Address::generate(&env),
+            &1u64,
+            &AwardReason::Migration,
+        );
+        client.award_if_eligible(&admin, &user, &2u64, &Bytes::new(&env));
+        assert_eq!(client.issuer_stats(&admin), 3);
+
+        // Failed awards are not counted.
+        assert!(client.try_award_badge(&admin, &user, &1u64).is_err());
+        assert_eq!(client.issuer_stats(&admin), 3);
+        assert_eq!(client.issuer_stats(&user), 0);
+    }
+
+    #[test]
+    fn test_issuer_stats_ignores_self_service_claims() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 152), &0i128);
+        client.offer_badge(&admin, &user, &1u64);
+        client.accept_badge(&user, &1u64);
+
+        assert_eq!(client.issuer_stats(&admin), 0);
+        assert_eq!(client.issuer_stats(&user), 0);
+    }
+
+    // ------------------------------------------------------------------
+    // External verifier
+    // ------------------------------------------------------------------
+
+    /// Accepts any proof equal to `b"ok"`.
+    #[contract]
+    struct MockVerifier;
+
+    #[contractimpl]
+    impl MockVerifier {
+        pub fn verify(env: Env, _user: Address, proof: Bytes) -> bool {
+            proof == Bytes::from_slice(&env, b"ok")
+        }
+    }
+
+    #[test]
+    fn test_award_if_eligible_consults_verifier() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let verifier = env.register(MockVerifier, ());
+        client.define_badge(&admin, &1u64, &make_hash(&env, 140), &0i128);
+        client.set_badge_verifier(&admin, &1u64, &Some(verifier.clone()));
+        assert_eq!(
+            env.events().all(),
+            [VerifierSet {
+                badge_id: 1,
+                verifier: Some(verifier)
+            }
+            .to_xdr(&env, &client.address)]
+        );
+
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_award_if_eligible(&admin, &user, &1u64, &Bytes::from_slice(&env, b"no")),
+            Err(Ok(Error::NotEligible))
+        );
+        assert_eq!(client.badges_of(&user).len(), 0);
+
+        client.award_if_eligible(&admin, &user, &1u64, &Bytes::from_slice(&env, b"ok"));
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+    }
+
+    #[test]
+    fn test_award_if_eligible_without_verifier_awards_directly() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let verifier = env.register(MockVerifier, ());
+        client.define_badge(&admin, &1u64, &make_hash(&env, 141), &0i128);
+        client.set_badge_verifier(&admin, &1u64, &Some(verifier));
+        client.set_badge_verifier(&admin, &1u64, &None);
+
+        let user = Address::generate(&env);
+        client.award_if_eligible(&admin, &user, &1u64, &Bytes::new(&env));
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+        assert_eq!(
+            client.try_set_badge_verifier(&admin, &2u64, &None),
+            Err(Ok(Error::BadgeNotFound))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // Verbose storage events
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_storage_bumped_events_when_verbose() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 130), &0i128);
+        client.set_verbose_storage_events(&admin, &true);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                StorageBumped {
+                    key_kind: 2,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                StorageBumped {
+                    key_kind: 25,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                StorageBumped {
+                    key_kind: 11,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                StorageBumped {
+                    key_kind: 13,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                StorageBumped {
+                    key_kind: 21,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                StorageBumped {
+                    key_kind: 19,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                BadgeAwarded {
+                    user,
+                    badge_id: 1,
+                    reason: AwardReason::Manual,
+                    reward: 0,
+                    reward_token: no_reward_token(&env),
+                    reward_token_decimals: 7,
+                    note_hash: BytesN::from_array(&env, &[0u8; 32]),
+                }
+                .to_xdr(&env, &client.address),
+                StorageBumped {
+                    key_kind: 12,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_storage_bumped_events_off_by_default() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 131), &0i128);
+        let user = Address::generate(&env);
+        client.evaluate_user(&admin, &user, &1u64);
+        assert_eq!(env.events().all().events().len(), 0);
+
+        assert_eq!(
+            client.try_set_verbose_storage_events(&Address::generate(&env), &true),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // Event level
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_verbose_event_level_adds_detail() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 132), &0i128);
+        assert_eq!(client.event_level(), EventLevel::Standard);
+        client.set_event_level(&admin, &EventLevel::Verbose);
+        assert_eq!(client.event_level(), EventLevel::Verbose);
+
+        let user = Address::generate(&env);
+        client.evaluate_user(&admin, &user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                StorageBumped {
+                    key_kind: 5,
+                    new_ttl: PERSISTENT_BUMP_LEDGERS
+                }
+                .to_xdr(&env, &client.address),
+                UserEvaluated { user, badge_id: 1 }.to_xdr(&env, &client.address),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minimal_event_level_keeps_core_events() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 133), &0i128);
+        client.set_verbose_storage_events(&admin, &true);
+        client.set_event_level(&admin, &EventLevel::Minimal);
+
+        let user = Address::generate(&env);
+        client.offer_badge(&admin, &user, &1u64);
+        assert_eq!(env.events().all().events().len(), 0);
+
+        client.accept_badge(&user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [BadgeAwarded {
+                user: user.clone(),
+                badge_id: 1,
+                reason: AwardReason::Manual,
+                reward: 0,
+                reward_token: no_reward_token(&env),
+                reward_token_decimals: 7,
+                note_hash: BytesN::from_array(&env, &[0u8; 32]),
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert_eq!(
+            client.try_set_event_level(&Address::generate(&env), &EventLevel::Verbose),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // Limited supply
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_max_supply_limits_holders() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            max_supply: Some(2),
+            ..BadgeDefinition::new(make_hash(&env, 120), 0)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+        assert_eq!(client.remaining_supply(&1u64), Some(2));
+
+        client.award_badge(&admin, &Address::generate(&env), &1u64);
+        assert_eq!(client.remaining_supply(&1u64), Some(1));
+        client.award_badge(&admin, &Address::generate(&env), &1u64);
+        assert_eq!(client.remaining_supply(&1u64), Some(0));
+        assert_eq!(client.holder_count(&1u64), 2);
+
+        assert_eq!(
+            client.try_award_badge(&admin, &Address::generate(&env), &1u64),
+            Err(Ok(Error::SupplyExhausted))
+        );
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+    }
+
+    #[test]
+    fn test_unlimited_supply_by_default() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 121), &0i128);
+        for _ in 0..3 {
+            client.award_badge(&admin, &Address::generate(&env), &1u64);
+        }
+        assert_eq!(client.holder_count(&1u64), 3);
+        assert_eq!(client.remaining_supply(&1u64), None);
+        assert_eq!(client.remaining_supply(&2u64), None);
+    }
+
+    // ------------------------------------------------------------------
+    // Family cooldowns
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_family_cooldown_blocks_early_award() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        for (badge_id, seed) in [(1u64, 110u8), (2, 111), (3, 112)] {
+            let definition = BadgeDefinition {
+                badge_family: 7,
+                cooldown_ledgers: 100,
+                ..BadgeDefinition::new(make_hash(&env, seed), 0)
+            };
+            client.define_badge_full(&admin, &badge_id, &definition);
+        }
+
+        let user = Address::generate(&env);
+        env.ledger().set_sequence_number(500);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.cooldown_remaining(&user, &7u32), 100);
+
+        env.ledger().set_sequence_number(560);
+        assert_eq!(client.cooldown_remaining(&user, &7u32), 40);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &2u64),
+            Err(Ok(Error::CooldownActive))
+        );
+
+        // Other users and other families are unaffected.
+        client.award_badge(&admin, &Address::generate(&env), &2u64);
+        assert_eq!(client.cooldown_remaining(&user, &8u32), 0);
+
+        env.ledger().set_sequence_number(600);
+        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64, 2u64]);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &3u64),
+            Err(Ok(Error::CooldownActive))
+        );
+    }
+
+    #[test]
+    fn test_cooldown_requires_family() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            cooldown_ledgers: 10,
+            ..BadgeDefinition::new(make_hash(&env, 113), 0)
+        };
+        assert_eq!(
+            client.try_define_badge_full(&admin, &1u64, &definition),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // Offers
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_offer_then_accept_awards_badge() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 90), &25i128);
+
+        client.offer_badge(&admin, &user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                BadgeOffered {
+                    user: user.clone(),
+                    badge_id: 1
+                }
+                .to_xdr(&env, &client.address),
+                RewardEscrowed {
+                    user: user.clone(),
+                    badge_id: 1,
+                    amount: 25
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+        assert!(client.is_offered(&user, &1u64));
+        assert_eq!(client.badges_of(&user).len(), 0);
+        assert_eq!(client.escrowed_reward(&user, &1u64), 25);
+        assert_eq!(client.claimable(&user), 0);
+
+        client.accept_badge(&user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                BadgeAwarded {
+                    user: user.clone(),
+                    badge_id: 1,
+                    reason: AwardReason::Manual,
+                    reward: 25,
+                    reward_token: no_reward_token(&env),
+                    reward_token_decimals: 7,
+                    note_hash: BytesN::from_array(&env, &[0u8; 32]),
+                }
+                .to_xdr(&env, &client.address),
+                RewardDue {
+                    user: user.clone(),
+                    badge_id: 1,
+                    amount: 25,
+                    token: None
+                }
+                .to_xdr(&env, &client.address),
+                BadgeAccepted {
+                    user: user.clone(),
+                    badge_id: 1
+                }
+                .to_xdr(&env, &client.address),
+                RewardReleased {
+                    user: user.clone(),
+                    badge_id: 1,
+                    amount: 25
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+        assert!(!client.is_offered(&user, &1u64));
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+        assert_eq!(client.escrowed_reward(&user, &1u64), 0);
+        assert_eq!(client.claimable(&user), 25);
+    }
+
+    #[test]
+    fn test_accept_badge_pays_escrowed_amount() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        // The offer escrows 40 and keeps the definition from being swapped
+        // out underneath it.
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 96), &40i128);
+        client.offer_badge(&admin, &user, &1u64);
+        assert_eq!(
+            client.try_delete_badge_definition(&admin, &1u64),
+            Err(Ok(Error::BadgeLocked))
+        );
+
+        client.accept_badge(&user, &1u64);
+        assert_eq!(client.claimable(&user), 40);
+    }
+
+    #[test]
+    fn test_pending_offers_tracks_offer_lifecycle() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        assert!(client.pending_offers(&user).is_empty());
+        for id in 1u64..=3 {
+            client.define_badge(&admin, &id, &make_hash(&env, 94 + id as u8), &0i128);
+            client.offer_badge(&admin, &user, &id);
+        }
+        client.offer_badge(&admin, &user, &2u64);
+        assert_eq!(
+            client.pending_offers(&user),
+            soroban_sdk::vec![&env, 1u64, 2, 3]
+        );
+
+        client.accept_badge(&user, &2u64);
+        assert_eq!(
+            client.pending_offers(&user),
+            soroban_sdk::vec![&env, 1u64, 3]
+        );
+        client.decline_badge(&user, &1u64);
+        client.decline_badge(&user, &3u64);
+        assert!(client.pending_offers(&user).is_empty());
+        env.as_contract(&client.address, || {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&DataKey::UserPending(user.clone())));
+        });
+    }
+
+    #[test]
+    fn test_decline_badge_forfeits_escrowed_reward() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 93), &40i128);
+        client.offer_badge(&admin, &user, &1u64);
+
+        client.decline_badge(&user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                BadgeDeclined {
+                    user: user.clone(),
+                    badge_id: 1
+                }
+                .to_xdr(&env, &client.address),
+                RewardForfeited {
+                    user: user.clone(),
+                    badge_id: 1,
+                    amount: 40
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+        assert_eq!(client.escrowed_reward(&user, &1u64), 0);
+        assert_eq!(client.claimable(&user), 0);
+    }
+
+    #[test]
+    fn test_decline_badge_clears_offer() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 91), &0i128);
+        client.offer_badge(&admin, &user, &1u64);
+
+        client.decline_badge(&user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [BadgeDeclined {
+                user: user.clone(),
+                badge_id: 1
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert_eq!(client.badges_of(&user).len(), 0);
+        assert_eq!(
+            client.try_accept_badge(&user, &1u64),
+            Err(Ok(Error::NoPendingOffer))
+        );
+        assert_eq!(
+            client.try_decline_badge(&user, &1u64),
+            Err(Ok(Error::NoPendingOffer))
+        );
+    }
+
+    #[test]
+    fn test_accept_badge_applies_duplicate_guard() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 92), &0i128);
+        client.offer_badge(&admin, &user, &1u64);
+        client.award_badge(&admin, &user, &1u64);
+
+        assert_eq!(
+            client.try_accept_badge(&user, &1u64),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+        assert!(client.is_offered(&user, &1u64));
+        assert_eq!(
+            client.try_offer_badge(&admin, &user, &2u64),
+            Err(Ok(Error::BadgeNotFound))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // migrate
+    // ------------------------------------------------------------------
+
+    /// Make the contract look like a version 1 deployment holding badge 1
+    /// (and a holder) under the original layout.
+    fn seed_v1_badge(env: &Env, client: &AchievementBadgeClient, user: &Address) {
+        env.as_contract(&client.address, || {
+            env.storage().instance().remove(&DataKey::SchemaVersion);
+            env.storage().persistent().set(
+                &DataKey::Badge(1),
+                &BadgeDefinitionV1 {
+                    criteria_hash: make_hash(env, 170),
+                    reward: 9,
+                },
+            );
+            env.storage().persistent().set(
+                &DataKey::UserBadges(user.clone()),
+                &soroban_sdk::vec![env, 1u64],
+            );
+        });
+    }
+
+    #[test]
+    fn test_migrate_rewrites_v1_definitions() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+        let user = Address::generate(&env);
+        seed_v1_badge(&env, &client, &user);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 171), &0i128);
+        assert_eq!(client.schema_version(), 1);
+
+        assert_eq!(
+            client.try_migrate(&admin, &2u32, &soroban_sdk::vec![&env, 1u64], &true),
+            Err(Ok(Error::SchemaVersionMismatch))
+        );
+        assert_eq!(
+            client.migrate(
+                &admin,
+                &1u32,
+                &soroban_sdk::vec![&env, 1u64, 2u64, 3u64],
+                &true
+            ),
+            1
+        );
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+        let summary = client.get_badge_summary(&1u64);
+        assert!(summary.found);
+        assert_eq!(summary.reward, 9);
+        assert_eq!(
+            client.badges_by_hash(&make_hash(&env, 170)),
+            soroban_sdk::vec![&env, 1u64]
+        );
+        assert!(client.get_claim_status(&user, &1u64).claimed);
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+        assert_eq!(client.storage_stats().badges, 2);
+
+        // Pre-existing holders are counted once `recompute_holders` runs.
+        assert_eq!(client.holder_count(&1u64), 0);
+        assert_eq!(client.storage_stats().holdings, 0);
+        client.recompute_holders(&admin, &1u64, &soroban_sdk::vec![&env, user.clone()]);
+        assert_eq!(client.holder_count(&1u64), 1);
+        assert_eq!(client.storage_stats().holdings, 1);
+
+        // The pre-existing holder is still protected by the duplicate guard.
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+    }
+
+    #[test]
+    fn test_migrate_rewrites_v2_definitions() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 172), &4i128);
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::SchemaVersion, &2u32);
+            env.storage().persistent().set(
+                &DataKey::Badge(1),
+                &BadgeDefinitionV2 {
+                    criteria_hash: make_hash(&env, 172),
+                    hash_algo: HashAlgo::Keccak256,
+                    reward: 4,
+                    require_evaluation: false,
+                    award_window: AwardWindow::Always,
+                    badge_family: 0,
+                    cooldown_ledgers: 0,
+                    max_supply: Some(10),
+                    verifier: None,
+                },
+            );
+        });
+
+        assert_eq!(
+            client.migrate(&admin, &2u32, &soroban_sdk::vec![&env, 1u64], &true),
+            1
+        );
+        assert_eq!(client.badge_name(&1u64), None);
+        assert_eq!(client.remaining_supply(&1u64), Some(10));
+        // Version 2 entries were already indexed.
+        assert_eq!(
+            client.badges_by_hash(&make_hash(&env, 172)),
+            soroban_sdk::vec![&env, 1u64]
+        );
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+    }
+
+    #[test]
+    fn test_migrate_rewrites_v3_definitions() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let name = String::from_str(&env, "Veteran");
+        client.define_badge(&admin, &1u64, &make_hash(&env, 173), &4i128);
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::SchemaVersion, &3u32);
+            env.storage().persistent().set(
+                &DataKey::Badge(1),
+                &BadgeDefinitionV3 {
+                    criteria_hash: make_hash(&env, 173),
+                    hash_algo: HashAlgo::Sha256,
+                    reward: 4,
+                    require_evaluation: false,
+                    award_window: AwardWindow::Always,
+                    badge_family: 0,
+                    cooldown_ledgers: 0,
+                    max_supply: None,
+                    verifier: None,
+                    name: Some(name.clone()),
+                },
+            );
+        });
+
+        assert_eq!(
+            client.migrate(&admin, &3u32, &soroban_sdk::vec![&env, 1u64], &true),
+            1
+        );
+        assert_eq!(client.badge_name(&1u64), Some(name));
+        assert_eq!(client.get_badge_summary(&1u64).reward, 4);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.claimable(&user), 4);
+    }
+
+    #[test]
+    fn test_migrate_rewrites_v4_definitions() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 174), &4i128);
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::SchemaVersion, &4u32);
+            env.storage().persistent().set(
+                &DataKey::Badge(1),
+                &BadgeDefinitionV4 {
+                    criteria_hash: make_hash(&env, 174),
+                    hash_algo: HashAlgo::Sha256,
+                    reward: 4,
+                    require_evaluation: false,
+                    award_window: AwardWindow::Always,
+                    badge_family: 0,
+                    cooldown_ledgers: 0,
+                    max_supply: None,
+                    verifier: None,
+                    name: None,
+                    reward_token: None,
+                },
+            );
+        });
+
+        assert_eq!(
+            client.migrate(&admin, &4u32, &soroban_sdk::vec![&env, 1u64], &true),
+            1
+        );
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.claimable(&user), 4);
+    }
+
+    #[test]
+    fn test_migrate_rewrites_v5_definitions() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 175), &4i128);
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::SchemaVersion, &5u32);
+            env.storage().persistent().set(
+                &DataKey::Badge(1),
+                &BadgeDefinitionV5 {
+                    criteria_hash: make_hash(&env, 175),
+                    hash_algo: HashAlgo::Sha256,
+                    reward: 4,
+                    require_evaluation: false,
+                    award_window: AwardWindow::Always,
+                    badge_family: 0,
+                    cooldown_ledgers: 0,
+                    max_supply: None,
+                    verifier: None,
+                    name: None,
+                    reward_token: None,
+                    scope: BadgeScope::Global,
+                },
+            );
+        });
+
+        assert_eq!(
+            client.migrate(&admin, &5u32, &soroban_sdk::vec![&env, 1u64], &true),
+            1
+        );
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+    }
+
+    #[test]
+    fn test_migrate_is_idempotent() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        seed_v1_badge(&env, &client, &Address::generate(&env));
+        assert_eq!(
+            client.migrate(&admin, &1u32, &soroban_sdk::vec![&env, 1u64], &false),
+            1
+        );
+        assert_eq!(
+            client.migrate(&admin, &1u32, &soroban_sdk::vec![&env, 1u64], &true),
+            0
+        );
+        assert_eq!(client.badges_by_hash(&make_hash(&env, 170)).len(), 1);
+
+        // Once finished, only the current version is accepted.
+        assert_eq!(
+            client.try_migrate(&admin, &1u32, &soroban_sdk::vec![&env, 1u64], &true),
+            Err(Ok(Error::SchemaVersionMismatch))
+        );
+        assert_eq!(
+            client.migrate(
+                &admin,
+                &SCHEMA_VERSION,
+                &soroban_sdk::vec![&env, 1u64],
+                &true
+            ),
+            0
+        );
+
+        assert_eq!(
+            client.try_migrate(
+                &Address::generate(&env),
+                &1u32,
+                &soroban_sdk::vec![&env],
+                &true
+            ),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
+
+    #[test]
+    fn test_migrate_in_batches() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        seed_v1_badge(&env, &client, &Address::generate(&env));
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(
+                &DataKey::Badge(5),
+                &BadgeDefinitionV1 {
+                    criteria_hash: make_hash(&env, 172),
+                    reward: 3,
+                },
+            );
+        });
+
+        assert_eq!(
+            client.migrate(&admin, &1u32, &soroban_sdk::vec![&env, 1u64], &false),
+            1
+        );
+        assert_eq!(client.schema_version(), 1);
+        assert!(client.get_badge_summary(&1u64).found);
+
+        // The second batch still reaches entries the first one skipped.
+        assert_eq!(
+            client.migrate(&admin, &1u32, &soroban_sdk::vec![&env, 1u64, 5u64], &true),
+            1
+        );
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+        let summary = client.get_badge_summary(&5u64);
+        assert!(summary.found);
+        assert_eq!(
+            client.badges_by_hash(&make_hash(&env, 172)),
+            soroban_sdk::vec![&env, 5u64]
+        );
+        assert_eq!(client.storage_stats().badges, 2);
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+    }
+
+    // ------------------------------------------------------------------
+    // check_invariants
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_check_invariants_holds_for_normal_use() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+
+        let user = Address::generate(&env);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 80), &10i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 80), &0i128);
+        client.define_meta_badge(&admin, &3u64, &soroban_sdk::vec![&env, 1u64, 2u64], &0i128);
+        client.award_badge(&admin, &user, &1u64);
+        client.offer_badge(&admin, &user, &2u64);
+        let users = soroban_sdk::vec![&env, user, Address::generate(&env)];
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &users);
+
+        client.renounce_admin(&admin);
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &users);
+    }
+
+    #[test]
+    fn test_stackable_badge_counts_repeat_awards() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            stackable: true,
+            ..BadgeDefinition::new(make_hash(&env, 120), 5)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 121), &0i128);
+
+        let user = Address::generate(&env);
+        assert_eq!(client.badge_quantity(&user, &1u64), 0);
+        client.award_badge(&admin, &user, &1u64);
+        client.award_badge(&admin, &user, &1u64);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.badge_quantity(&user, &1u64), 3);
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+        assert_eq!(client.holder_count(&1u64), 1);
+        // Every award pays the reward.
+        assert_eq!(client.claimable(&user), 15);
+        assert_eq!(
+            client.try_set_badge_vesting(&admin, &1u64, &0u32, &10u32),
+            Err(Ok(Error::InvalidInput))
+        );
+
+        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(client.badge_quantity(&user, &2u64), 1);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &2u64),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, user.clone()], &1u64);
+        assert_eq!(client.badge_quantity(&user, &1u64), 0);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.badge_quantity(&user, &1u64), 1);
+    }
+
+    #[test]
+    fn test_top_holders_ranks_by_points() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        for id in 1u64..=3 {
+            client.define_badge(&admin, &id, &make_hash(&env, 100 + id as u8), &0i128);
+        }
+        let (alice, bob, carol) = (
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        );
+        client.award_badge(&admin, &alice, &1u64);
+        client.award_badge(&admin, &bob, &1u64);
+        client.award_badge(&admin, &bob, &2u64);
+        client.award_badge(&admin, &carol, &1u64);
+        client.award_badge(&admin, &carol, &2u64);
+        client.award_badge(&admin, &carol, &3u64);
+
+        assert_eq!(
+            client.top_holders(&10u32),
+            soroban_sdk::vec![
+                &env,
+                (carol.clone(), 3u32),
+                (bob.clone(), 2),
+                (alice.clone(), 1)
+            ]
+        );
+        assert_eq!(
+            client.top_holders(&1u32),
+            soroban_sdk::vec![&env, (carol.clone(), 3u32)]
+        );
+
+        // Revocation moves a user down; ties keep the earlier arrival first.
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, carol.clone()], &3u64);
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, alice.clone()], &1u64);
+        assert_eq!(
+            client.top_holders(&10u32),
+            soroban_sdk::vec![&env, (bob.clone(), 2u32), (carol.clone(), 2)]
+        );
+
+        client.set_private(&bob, &true);
+        assert_eq!(
+            client.top_holders(&10u32),
+            soroban_sdk::vec![&env, (carol, 2u32)]
+        );
+    }
+
+    #[test]
+    fn test_top_holders_evicts_lowest_when_full() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 110), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 111), &0i128);
+        let mut users = soroban_sdk::Vec::new(&env);
+        for _ in 0..MAX_PAGE_SIZE {
+            let user = Address::generate(&env);
+            client.award_badge(&admin, &user, &1u64);
+            users.push_back(user);
+        }
+
+        // A late single-badge holder ties the lowest and does not displace it.
+        let climber = Address::generate(&env);
+        client.award_badge(&admin, &climber, &1u64);
+        let board = client.top_holders(&MAX_PAGE_SIZE);
+        assert_eq!(board.len(), MAX_PAGE_SIZE);
+        assert!(!board.iter().any(|(user, _)| user == climber));
+
+        client.award_badge(&admin, &climber, &2u64);
+        let board = client.top_holders(&100u32);
+        assert_eq!(board.len(), MAX_PAGE_SIZE);
+        assert_eq!(board.get(0).unwrap(), (climber, 2));
+        assert_eq!(
+            board.last().unwrap(),
+            (users.get(MAX_PAGE_SIZE - 2).unwrap(), 1)
+        );
+
+        // The board is approximate: a slot freed by a demotion is not refilled
+        // with the user evicted earlier, who still holds a badge.
+        let evicted = users.get(MAX_PAGE_SIZE - 1).unwrap();
+        client.revoke_badge_batch(
+            &admin,
+            &soroban_sdk::vec![&env, users.get(0).unwrap()],
+            &1u64,
+        );
+        let board = client.top_holders(&100u32);
+        assert_eq!(board.len(), MAX_PAGE_SIZE - 1);
+        assert!(!board.iter().any(|(user, _)| user == evicted));
+    }
+
+    #[test]
+    fn test_recompute_holders_repairs_desynced_count() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 83), &0i128);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        client.award_badge(&admin, &alice, &1u64);
+        client.award_badge(&admin, &bob, &1u64);
+
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::HolderCount(1), &7u32);
+        });
+        assert_eq!(client.holder_count(&1u64), 7);
+
+        let users = soroban_sdk::vec![&env, alice.clone(), carol, bob, alice];
+        assert_eq!(client.recompute_holders(&admin, &1u64, &users), 2);
+        assert_eq!(
+            env.events().all(),
+            [HolderCountRepaired {
+                badge_id: 1,
+                previous: 7,
+                holders: 2
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert_eq!(client.holder_count(&1u64), 2);
+    }
+
+    #[test]
+    fn test_recompute_holders_requires_admin_and_badge() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let users = soroban_sdk::vec![&env, Address::generate(&env)];
+        assert_eq!(
+            client.try_recompute_holders(&admin, &1u64, &users),
+            Err(Ok(Error::BadgeNotFound))
+        );
+        client.define_badge(&admin, &1u64, &make_hash(&env, 84), &0i128);
+        assert_eq!(
+            client.try_recompute_holders(&Address::generate(&env), &1u64, &users),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
+
+    #[test]
+    fn test_check_invariants_detects_corruption() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 81), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 82), &0i128);
+
+        // A definition lost from storage while still indexed.
+        env.as_contract(&client.address, || {
+            env.storage().persistent().remove(&DataKey::Badge(2));
+        });
+        assert_eq!(
+            client.try_check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]),
+            Err(Ok(Error::InvariantViolation))
+        );
+
+        // Paging only inspects the requested index positions.
+        client.check_invariants(&0u32, &1u32, &soroban_sdk::vec![&env]);
+        assert_eq!(
+            client.try_check_invariants(&1u32, &1u32, &soroban_sdk::vec![&env]),
+            Err(Ok(Error::InvariantViolation))
+        );
+        client.check_invariants(&2u32, &1u32, &soroban_sdk::vec![&env]);
+
+        // A badge counter out of step with the index fails on every page.
+        env.as_contract(&client.address, || {
+            env.storage().instance().set(&DataKey::BadgeCount, &3u64);
+        });
+        assert_eq!(
+            client.try_check_invariants(&0u32, &1u32, &soroban_sdk::vec![&env]),
+            Err(Ok(Error::InvariantViolation))
+        );
+    }
+
+    #[test]
+    fn test_check_invariants_detects_counter_and_reward_corruption() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 85), &10i128);
+        client.define_meta_badge(&admin, &2u64, &soroban_sdk::vec![&env, 1u64], &0i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        let users = soroban_sdk::vec![&env, user.clone()];
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &users);
+
+        // The meta-badge counter is compared exactly only by a full page.
+        env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::MetaBadgeCount, &2u64);
+        });
+        client.check_invariants(&0u32, &1u32, &soroban_sdk::vec![&env]);
+        assert_eq!(
+            client.try_check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]),
+            Err(Ok(Error::InvariantViolation))
+        );
+        env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::MetaBadgeCount, &1u64);
+        });
+
+        // A negative committed reward fails only when its user is checked.
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Claimable(user.clone()), &-1i128);
+        });
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+        assert_eq!(
+            client.try_check_invariants(&0u32, &MAX_PAGE_SIZE, &users),
+            Err(Ok(Error::InvariantViolation))
+        );
+
+        let mut too_many = soroban_sdk::vec![&env];
+        for _ in 0..=MAX_PAGE_SIZE {
+            too_many.push_back(user.clone());
+        }
+        assert_eq!(
+            client.try_check_invariants(&0u32, &MAX_PAGE_SIZE, &too_many),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
+
+    #[test]
+    fn test_check_invariants_detects_admin_config_mismatch() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+
+        env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .set(&DataKey::AdminRenounced, &true);
+        });
+        assert_eq!(
+            client.try_check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]),
+            Err(Ok(Error::InvariantViolation))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // Merkle claim roots
+    // ------------------------------------------------------------------
+
+    fn leaf(env: &Env, user: &Address) -> BytesN<32> {
+        env.crypto().sha256(&user.clone().to_xdr(env)).into()
+    }
+
+    #[test]
+    fn test_claim_badge_with_valid_proof() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let users: [Address; 4] = core::array::from_fn(|_| Address::generate(&env));
+        let leaves: [BytesN<32>; 4] = core::array::from_fn(|i| leaf(&env, &users[i]));
+        let left = hash_pair(&env, &leaves[0], &leaves[1]);
+        let right = hash_pair(&env, &leaves[2], &leaves[3]);
+        let root = hash_pair(&env, &left, &right);
+
+        client.define_badge_with_claim_root(&admin, &1u64, &make_hash(&env, 70), &10i128, &root);
+
+        let proof = soroban_sdk::vec![&env, leaves[3].clone(), left.clone()];
+        client.claim_badge(&users[2], &1u64, &proof);
+        assert!(client.get_claim_status(&users[2], &1u64).claimed);
+
+        // A second claim hits the duplicate guard.
+        assert_eq!(
+            client.try_claim_badge(&users[2], &1u64, &proof),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+    }
+
+    #[test]
+    fn test_claim_badge_rejects_invalid_proof() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let member = Address::generate(&env);
+        let sibling = Address::generate(&env);
+        let root = hash_pair(&env, &leaf(&env, &member), &leaf(&env, &sibling));
+        client.define_badge_with_claim_root(&admin, &1u64, &make_hash(&env, 71), &0i128, &root);
+
+        let outsider = Address::generate(&env);
+        let proof = soroban_sdk::vec![&env, leaf(&env, &sibling)];
+        assert_eq!(
+            client.try_claim_badge(&outsider, &1u64, &proof),
+            Err(Ok(Error::InvalidProof))
+        );
+
+        // Badges without a root cannot be claimed.
+        client.define_badge(&admin, &2u64, &make_hash(&env, 72), &0i128);
+        assert_eq!(
+            client.try_claim_badge(&member, &2u64, &proof),
+            Err(Ok(Error::BadgeNotFound))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // Meta badges
+    // ------------------------------------------------------------------
+
+    fn setup_meta(env: &Env) -> (AchievementBadgeClient<'_>, Address) {
+        let (client, admin, _) = setup(env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(env, 60), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(env, 61), &0i128);
+        client.define_meta_badge(
+            &admin,
+            &100u64,
+            &soroban_sdk::vec![env, 1u64, 2u64],
+            &500i128,
+        );
+
+        (client, admin)
+    }
+
+    #[test]
+    fn test_claim_meta_awards_when_prerequisites_held() {
+        let env = Env::default();
+        let (client, admin) = setup_meta(&env);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(
+            client.try_claim_meta(&user, &100u64),
+            Err(Ok(Error::PrerequisitesNotMet))
+        );
+
+        client.award_badge(&admin, &user, &2u64);
+        client.claim_meta(&user, &100u64);
+
+        assert!(client.get_claim_status(&user, &100u64).claimed);
+        assert_eq!(client.get_badge_summary(&100u64).reward, 500);
+        assert_eq!(
+            client.meta_requirements(&100u64),
+            soroban_sdk::vec![&env, 1u64, 2u64]
+        );
+
+        // Duplicate guard still applies.
+        assert_eq!(
+            client.try_claim_meta(&user, &100u64),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+    }
+
+    #[test]
+    fn test_claim_meta_rejects_plain_badge() {
+        let env = Env::default();
+        let (client, _) = setup_meta(&env);
+
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_claim_meta(&user, &1u64),
+            Err(Ok(Error::BadgeNotFound))
+        );
+    }
+
+    #[test]
+    fn test_meta_progress_counts_held_prerequisites() {
+        let env = Env::default();
+        let (client, admin) = setup_meta(&env);
+
+        let user = Address::generate(&env);
+        assert_eq!(client.meta_progress(&user, &100u64), (0, 2));
+
+        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(client.meta_progress(&user, &100u64), (1, 2));
+
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.meta_progress(&user, &100u64), (2, 2));
+    }
+
+    #[test]
+    fn test_meta_eligible_filters_users() {
+        let env = Env::default();
+        let (client, admin) = setup_meta(&env);
+
+        let (alice, bob, carol) = (
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        );
+        for user in [&alice, &carol] {
+            client.award_badge(&admin, user, &1u64);
+            client.award_badge(&admin, user, &2u64);
+        }
+        client.award_badge(&admin, &bob, &1u64);
+        client.set_private(&carol, &true);
+
+        let users = soroban_sdk::vec![&env, alice.clone(), bob.clone(), carol.clone()];
+        assert_eq!(
+            client.meta_eligible(&100u64, &users),
+            soroban_sdk::vec![&env, alice, carol]
+        );
+        assert_eq!(client.meta_eligible(&1u64, &users), soroban_sdk::vec![&env]);
+        assert_eq!(
+            client.meta_eligible(&999u64, &users),
+            soroban_sdk::vec![&env]
+        );
+    }
+
+    #[test]
+    fn test_meta_eligible_caps_input() {
+        let env = Env::default();
+        let (client, admin) = setup_meta(&env);
+
+        let mut users = soroban_sdk::vec![&env];
+        for _ in 0..=MAX_PAGE_SIZE {
+            let user = Address::generate(&env);
+            client.award_badge(&admin, &user, &1u64);
+            client.award_badge(&admin, &user, &2u64);
+            users.push_back(user);
+        }
+        assert_eq!(client.meta_eligible(&100u64, &users).len(), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_meta_progress_undefined_meta_badge() {
+        let env = Env::default();
+        let (client, admin) = setup_meta(&env);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.meta_progress(&user, &1u64), (0, 0));
+        assert_eq!(client.meta_progress(&user, &999u64), (0, 0));
+    }
+
+    #[test]
+    fn test_define_meta_badge_validates_prerequisites() {
+        let env = Env::default();
+        let (client, admin) = setup_meta(&env);
+
+        assert_eq!(
+            client.try_define_meta_badge(&admin, &101u64, &soroban_sdk::vec![&env], &0i128),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(
+            client.try_define_meta_badge(
+                &admin,
+                &101u64,
+                &soroban_sdk::vec![&env, 1u64, 101u64],
+                &0i128
+            ),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(
+            client.try_define_meta_badge(
+                &admin,
+                &101u64,
+                &soroban_sdk::vec![&env, 1u64, 999u64],
+                &0i128
+            ),
+            Err(Ok(Error::BadgeNotFound))
+        );
+        assert_eq!(
+            client.try_define_meta_badge(&admin, &1u64, &soroban_sdk::vec![&env, 2u64], &0i128),
+            Err(Ok(Error::BadgeAlreadyExists))
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 5. badges_of
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_badges_of_returns_empty_for_new_user() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+
+        let user = Address::generate(&env);
+        let badges = client.badges_of(&user);
+        assert_eq!(badges.len(), 0);
+    }
+
+    #[test]
+    fn test_private_user_hidden_from_public_reads() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 26), &0i128);
+        client.define_meta_badge(&admin, &2u64, &soroban_sdk::vec![&env, 1u64], &0i128);
+        let (user, stranger) = (Address::generate(&env), Address::generate(&env));
+        client.award_badge_with_source(&admin, &user, &1u64, &42u64);
+
+        client.set_private(&user, &true);
+        assert_eq!(
+            env.events().all(),
+            [PrivacySet {
+                user: user.clone(),
+                private: true
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert!(client.is_private(&user));
+        assert!(client.badges_of(&user).is_empty());
+        assert!(client.badges_of_detailed(&user).is_empty());
+        assert!(client.badges_of_as(&stranger, &user).is_empty());
+        assert_eq!(
+            client.badges_of_as(&user, &user),
+            soroban_sdk::vec![&env, 1u64]
+        );
+        assert_eq!(
+            client.badges_of_as(&admin, &user),
+            soroban_sdk::vec![&env, 1u64]
+        );
+
+        // Every per-user holdings getter answers as if nothing were held.
+        assert_eq!(client.meta_progress(&user, &2u64), (0, 1));
+        assert_eq!(
+            client.missing_badges(&user, &0u32, &MAX_PAGE_SIZE),
+            soroban_sdk::vec![&env, 1u64, 2u64]
+        );
+        assert!(!client.get_claim_status(&user, &1u64).claimed);
+        assert_eq!(
+            client.holds_badge_status(&user, &1u64),
+            HoldStatus::NoRecord
+        );
+        assert_eq!(client.badge_quantity(&user, &1u64), 0);
+        assert_eq!(client.award_ordinal(&user, &1u64), None);
+        assert_eq!(client.award_source(&user, &1u64), 0);
+        assert_eq!(client.last_active(&user), None);
+
+        // Duplicate guards and eligibility still see the real holdings.
+        assert!(client.holds_badge(&user, &1u64));
+        assert!(!client.holds_badge(&user, &2u64));
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::BadgeAlreadyAwarded))
+        );
+        assert_eq!(
+            client.meta_eligible(&2u64, &soroban_sdk::vec![&env, user.clone()]),
+            soroban_sdk::vec![&env, user.clone()]
+        );
+        client.claim_meta(&user, &2u64);
+        assert_eq!(
+            client.badges_of_as(&user, &user),
+            soroban_sdk::vec![&env, 1u64, 2u64]
+        );
+    }
+
+    #[test]
+    fn test_public_user_visible_to_everyone() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 27), &0i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        client.set_private(&user, &true);
+        client.set_private(&user, &false);
+
+        assert!(!client.is_private(&user));
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+        assert!(client.get_claim_status(&user, &1u64).claimed);
+        assert_eq!(client.holds_badge_status(&user, &1u64), HoldStatus::Holds);
+        assert_eq!(client.badge_quantity(&user, &1u64), 1);
+        assert_eq!(client.award_ordinal(&user, &1u64), Some(1));
+        assert_eq!(
+            client.badges_of_as(&Address::generate(&env), &user),
+            soroban_sdk::vec![&env, 1u64]
+        );
+    }
+
+    // ------------------------------------------------------------------
+    // 6. Full lifecycle
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_full_lifecycle() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+
+        // Define two badges.
+        client.define_badge(&admin, &1u64, &make_hash(&env, 11), &200i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 12), &0i128);
+
+        // Evaluate user against badge 1 (just auditing).
+        client.evaluate_user(&admin, &user, &1u64);
+
+        // Award badge 1.
+        client.award_badge(&admin, &user, &1u64);
+
+        // Evaluate and award badge 2.
+        client.evaluate_user(&admin, &user, &2u64);
+        client.award_badge(&admin, &user, &2u64);
+
+        let badges = client.badges_of(&user);
+        assert_eq!(badges.len(), 2);
+        assert_eq!(badges.get(0).unwrap(), 1u64);
+        assert_eq!(badges.get(1).unwrap(), 2u64);
+
+        // Duplicate award must fail.
+        assert!(client.try_award_badge(&admin, &user, &1u64).is_err());
+    }
+
+    // ------------------------------------------------------------------
+    // 7. get_badge_summary
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_get_badge_summary_known_badge_without_metadata() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 20);
+        client.define_badge(&admin, &5u64, &hash, &100i128);
+
+        let summary = client.get_badge_summary(&5u64);
+        assert!(summary.found);
+        assert_eq!(summary.badge_id, 5u64);
+        assert_eq!(summary.reward, 100i128);
+        assert_eq!(summary.criteria_hash, hash);
+        // Metadata not yet set — empty strings expected.
+        assert_eq!(summary.title, soroban_sdk::String::from_str(&env, ""));
+    }
+
+    #[test]
+    fn test_get_badge_summary_known_badge_with_metadata() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 21);
+        client.define_badge(&admin, &6u64, &hash, &0i128);
+
+        client.set_badge_metadata(
+            &admin,
+            &6u64,
+            &soroban_sdk::String::from_str(&env, "First Win"),
+            &soroban_sdk::String::from_str(&env, "Win your first game"),
+            &soroban_sdk::String::from_str(&env, "Win one ranked match"),
+        );
+
+        let summary = client.get_badge_summary(&6u64);
+        assert!(summary.found);
+        assert_eq!(
+            summary.title,
+            soroban_sdk::String::from_str(&env, "First Win")
+        );
+        assert_eq!(
+            summary.description,
+            soroban_sdk::String::from_str(&env, "Win your first game")
+        );
+        assert_eq!(
+            summary.award_rules,
+            soroban_sdk::String::from_str(&env, "Win one ranked match")
+        );
+    }
+
+    #[test]
+    fn test_get_badge_summary_unknown_badge_returns_not_found() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+
+        let summary = client.get_badge_summary(&9999u64);
+        assert!(!summary.found);
+        assert_eq!(summary.badge_id, 9999u64);
+        assert_eq!(summary.reward, 0i128);
+    }
+
+    #[test]
+    fn test_set_badge_metadata_requires_badge_defined() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        // Badge 42 not defined — should fail.
+        let result = client.try_set_badge_metadata(
+            &admin,
+            &42u64,
+            &soroban_sdk::String::from_str(&env, "Title"),
+            &soroban_sdk::String::from_str(&env, "Desc"),
+            &soroban_sdk::String::from_str(&env, "Rules"),
+        );
+        assert!(result.is_err());
+    }
+
+    // ------------------------------------------------------------------
+    // 8. get_claim_status
+    // ------------------------------------------------------------------
+
+    #[test]
+    fn test_get_claim_status_claimed_user() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 30);
+        client.define_badge(&admin, &7u64, &hash, &0i128);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &7u64);
+
+        let snapshot = client.get_claim_status(&user, &7u64);
+        assert!(snapshot.badge_found);
+        assert!(snapshot.claimed);
+        assert_eq!(snapshot.badge_id, 7u64);
+    }
+
+    #[test]
+    fn test_get_claim_status_unclaimed_user() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        let hash = make_hash(&env, 31);
+        client.define_badge(&admin, &8u64, &hash, &0i128);
+
+        let user = Address::generate(&env);
+        // User has not been awarded the badge.
+        let snapshot = client.get_claim_status(&user, &8u64);
+        assert!(snapshot.badge_found);
+        assert!(!snapshot.claimed);
+    }
+
+    #[test]
+    fn test_get_claim_status_unknown_badge() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
+
+        let user = Address::generate(&env);
+        let snapshot = client.get_claim_status(&user, &9999u64);
+        assert!(!snapshot.badge_found);
+        assert!(!snapshot.claimed);
+    }
+
+    #[test]
+    fn test_holds_badge_status_three_states() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &7u64, &make_hash(&env, 32), &0i128);
+        client.define_badge(&admin, &8u64, &make_hash(&env, 33), &0i128);
+
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.holds_badge_status(&user, &7u64),
+            HoldStatus::NoRecord
+        );
+
+        client.award_badge(&admin, &user, &7u64);
+        assert_eq!(client.holds_badge_status(&user, &7u64), HoldStatus::Holds);
+        assert_eq!(
+            client.holds_badge_status(&user, &8u64),
+            HoldStatus::DoesNotHold
+        );
+        assert_eq!(
+            client.holds_badge_status(&user, &9999u64),
+            HoldStatus::DoesNotHold
+        );
+    }
+
+    #[test]
+    fn test_last_active_tracks_latest_award() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 34), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 35), &0i128);
+
+        let user = Address::generate(&env);
+        assert_eq!(client.last_active(&user), None);
+
+        env.ledger().set_sequence_number(40);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.last_active(&user), Some(40));
+
+        env.ledger().set_sequence_number(75);
+        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(client.last_active(&user), Some(75));
+    }
+
+    // ------------------------------------------------------------------
+    // Reward claims
+    // ------------------------------------------------------------------
+
+    /// Register a reward token, point the contract at it, and mint `funds`
+    /// to the contract.
+    fn setup_reward_token(
+        env: &Env,
+        client: &AchievementBadgeClient,
+        admin: &Address,
+        funds: i128,
+    ) -> Address {
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(env, &token).mint(&client.address, &funds);
+        client.set_reward_token(admin, &token);
+        token
+    }
+
+    #[test]
+    fn test_awards_accrue_claimable_rewards() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 180), &40i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 181), &2i128);
+        client.define_badge(&admin, &3u64, &make_hash(&env, 182), &0i128);
+
+        let user = Address::generate(&env);
+        assert_eq!(client.claimable(&user), 0);
+        client.award_badge(&admin, &user, &1u64);
+        client.award_badge(&admin, &user, &2u64);
+        client.award_badge(&admin, &user, &3u64);
+        assert_eq!(client.claimable(&user), 42);
+    }
+
+    #[test]
+    fn test_claim_rewards_transfers_and_clears() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        let token = setup_reward_token(&env, &client, &admin, 100);
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 183), &40i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+
+        assert_eq!(client.claim_rewards(&user), 40);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&user), 40);
+        assert_eq!(token_client.balance(&client.address), 60);
+        assert_eq!(client.contract_balance(&token), 60);
+        assert_eq!(client.claimable(&user), 0);
+        assert_eq!(
+            client.try_claim_rewards(&user),
+            Err(Ok(Error::NothingToClaim))
+        );
+    }
+
+    #[test]
+    fn test_claim_rewards_emits_event() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        let token = setup_reward_token(&env, &client, &admin, 100);
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 184), &5i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        client.claim_rewards(&user);
+
+        let events = env.events().all();
+        assert!(events.events().contains(
+            &RewardClaimed {
+                user,
+                token,
+                amount: 5
+            }
+            .to_xdr(&env, &client.address)
+        ));
+    }
+
+    #[test]
+    fn test_reward_token_override_routes_rewards() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        let default_token = setup_reward_token(&env, &client, &admin, 100);
+        let other_token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(&env, &other_token).mint(&client.address, &50);
+
+        let mut definition = BadgeDefinition::new(make_hash(&env, 186), 7);
+        definition.reward_token = Some(other_token.clone());
+        client.define_badge_full(&admin, &1u64, &definition);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 187), &3i128);
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                BadgeAwarded {
+                    user: user.clone(),
+                    badge_id: 1,
+                    reason: AwardReason::Manual,
+                    reward: 7,
+                    reward_token: other_token.clone(),
+                    reward_token_decimals: 7,
+                    note_hash: BytesN::from_array(&env, &[0u8; 32]),
+                }
+                .to_xdr(&env, &client.address),
+                RewardDue {
+                    user: user.clone(),
+                    badge_id: 1,
+                    amount: 7,
+                    token: Some(other_token.clone())
+                }
+                .to_xdr(&env, &client.address)
+            ]
+        );
+        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(
+            env.events().all(),
+            [
+                BadgeAwarded {
+                    user: user.clone(),
+                    badge_id: 2,
+                    reason: AwardReason::Manual,
+                    reward: 3,
+                    reward_token: default_token.clone(),
+                    reward_token_decimals: 7,
+                    note_hash: BytesN::from_array(&env, &[0u8; 32]),
+                }
+                .to_xdr(&env, &client.address),
+                RewardDue {
+                    user: user.clone(),
+                    badge_id: 2,
+                    amount: 3,
+                    token: Some(default_token.clone())
                 }
+                .to_xdr(&env, &client.address)
+            ]
+        );
+
+        // A reward-free badge reports the sentinel even with a token set.
+        client.define_badge(&admin, &3u64, &make_hash(&env, 188), &0i128);
+        client.award_badge(&admin, &user, &3u64);
+        assert_eq!(
+            env.events().all(),
+            [BadgeAwarded {
+                user: user.clone(),
+                badge_id: 3,
+                reason: AwardReason::Manual,
+                reward: 0,
+                reward_token: no_reward_token(&env),
+                reward_token_decimals: 7,
+                note_hash: BytesN::from_array(&env, &[0u8; 32]),
             }
-        }
+            .to_xdr(&env, &client.address)]
+        );
+
+        assert_eq!(client.claimable(&user), 3);
+        assert_eq!(client.claimable_in(&user, &other_token), 7);
+        assert_eq!(client.claim_rewards_in(&user, &other_token), 7);
+        assert_eq!(client.claim_rewards(&user), 3);
+        assert_eq!(
+            soroban_sdk::token::TokenClient::new(&env, &other_token).balance(&user),
+            7
+        );
+        assert_eq!(
+            soroban_sdk::token::TokenClient::new(&env, &default_token).balance(&user),
+            3
+        );
+        assert_eq!(
+            client.try_claim_rewards_in(&user, &other_token),
+            Err(Ok(Error::NothingToClaim))
+        );
     }
 
-    // -----------------------------------------------------------------------
-    // get_claim_status
-    // -----------------------------------------------------------------------
+    #[test]
+    fn test_reward_token_override_must_be_a_token() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
 
-    /// Return the claim-status snapshot for `(user, badge_id)`.
-    ///
-    /// `badge_found` is `false` and `claimed` is `false` when the badge does
-    /// not exist. `claimed` is `false` when the badge exists but has not been
-    /// awarded to this user. Both fields are deterministic for all inputs.
-    pub fn get_claim_status(env: Env, user: Address, badge_id: u64) -> ClaimStatusSnapshot {
-        let badge_found = env.storage().persistent().has(&DataKey::Badge(badge_id));
+        let mut definition = BadgeDefinition::new(make_hash(&env, 188), 7);
+        definition.reward_token = Some(Address::generate(&env));
+        assert_eq!(
+            client.try_define_badge_full(&admin, &1u64, &definition),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
 
-        if !badge_found {
-            return ClaimStatusSnapshot { badge_id, claimed: false, badge_found: false };
-        }
+    #[test]
+    fn test_atomic_settlement_pays_on_award() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        let token = setup_reward_token(&env, &client, &admin, 100);
 
-        let badges: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::UserBadges(user))
-            .unwrap_or_else(|| vec![&env]);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 189), &15i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 191), &10i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
 
-        let claimed = badges.iter().any(|id| id == badge_id);
+        client.pause(&admin);
+        client.set_settlement_mode(&admin, &SettlementMode::Atomic);
+        client.unpause(&admin);
+        assert_eq!(client.settlement_mode(), SettlementMode::Atomic);
+        client.award_badge(&admin, &user, &2u64);
 
-        ClaimStatusSnapshot { badge_id, claimed, badge_found: true }
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&user), 10);
+        // The balance credited before the switch is still claimable.
+        assert_eq!(client.claimable(&user), 15);
+        assert_eq!(client.claim_rewards(&user), 15);
+        assert_eq!(token_client.balance(&client.address), 75);
     }
-}
 
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
+    #[test]
+    fn test_atomic_award_rejects_underfunded_contract() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        let token = setup_reward_token(&env, &client, &admin, 5);
+        client.pause(&admin);
+        client.set_settlement_mode(&admin, &SettlementMode::Atomic);
+        client.unpause(&admin);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 193), &10i128);
 
-fn require_initialized(env: &Env) -> Result<(), Error> {
-    if !env.storage().instance().has(&DataKey::Admin) {
-        return Err(Error::NotInitialized);
-    }
-    Ok(())
-}
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::InsufficientFunds))
+        );
+        assert!(client.badges_of(&user).is_empty());
+        assert_eq!(client.holder_count(&1u64), 0);
+
+        let funder = Address::generate(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&funder, &20);
+        client.fund_rewards(&funder, &token, &20i128);
+        assert!(env.events().all().events().contains(
+            &RewardsFunded {
+                from: funder.clone(),
+                token: token.clone(),
+                amount: 20
+            }
+            .to_xdr(&env, &client.address)
+        ));
+        assert_eq!(client.contract_balance(&token), 25);
 
-/// Verify that `caller` is the stored admin and has signed the invocation.
-fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
-    let admin: Address = env
-        .storage()
-        .instance()
-        .get(&DataKey::Admin)
-        .ok_or(Error::NotInitialized)?;
-    caller.require_auth();
-    if caller != &admin {
-        return Err(Error::NotAuthorized);
+        client.award_badge(&admin, &user, &1u64);
+        let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+        assert_eq!(token_client.balance(&user), 10);
+        assert_eq!(client.contract_balance(&token), 15);
     }
-    Ok(())
-}
 
-/// Fetch the badge definition or return `BadgeNotFound`.
-fn require_badge_exists(env: &Env, badge_id: u64) -> Result<BadgeDefinition, Error> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::Badge(badge_id))
-        .ok_or(Error::BadgeNotFound)
-}
+    #[test]
+    fn test_fund_rewards_rejects_non_positive_amount() {
+        let env = Env::default();
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
+        let token = setup_reward_token(&env, &client, &admin, 0);
+        let funder = Address::generate(&env);
+        assert_eq!(
+            client.try_fund_rewards(&funder, &token, &0i128),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
+    /// Reward token that reports ABI version 2 and pays from an unlimited
+    /// balance.
+    #[contract]
+    struct MockRewardToken;
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+    #[contractimpl]
+    impl MockRewardToken {
+        pub fn abi_version(_env: Env) -> u32 {
+            2
+        }
 
-    // ------------------------------------------------------------------
-    // Test helpers
-    // ------------------------------------------------------------------
+        pub fn balance(_env: Env, _id: Address) -> i128 {
+            i128::MAX
+        }
 
-    fn make_hash(env: &Env, seed: u8) -> BytesN<32> {
-        BytesN::from_array(env, &[seed; 32])
+        pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {}
     }
 
-    fn setup(env: &Env) -> (AchievementBadgeClient<'_>, Address, Address) {
+    /// Set up an atomic-settlement contract paying in a token that reports
+    /// ABI version 2, and which expects `expected_reward_abi`.
+    fn setup_atomic_with_reward_abi(
+        env: &Env,
+        expected_reward_abi: u32,
+    ) -> (AchievementBadgeClient<'_>, Address) {
+        let client = AchievementBadgeClient::new(env, &env.register(AchievementBadge, ()));
+        env.mock_all_auths();
         let admin = Address::generate(env);
-        let reward_contract = Address::generate(env);
+        let name = String::from_str(env, "Badges");
+        client.init(
+            &admin,
+            &Address::generate(env),
+            &7u32,
+            &name,
+            &expected_reward_abi,
+        );
+        client.set_reward_token(&admin, &env.register(MockRewardToken, ()));
+        client.pause(&admin);
+        client.set_settlement_mode(&admin, &SettlementMode::Atomic);
+        client.unpause(&admin);
+        client.define_badge(&admin, &1u64, &make_hash(env, 192), &10i128);
+        (client, admin)
+    }
 
-        let contract_id = env.register(AchievementBadge, ());
-        let client = AchievementBadgeClient::new(env, &contract_id);
+    #[test]
+    fn test_atomic_settlement_checks_reward_abi() {
+        let env = Env::default();
+        let (client, admin) = setup_atomic_with_reward_abi(&env, 2);
+        assert_eq!(client.reward_abi(), 2);
+        client.award_badge(&admin, &Address::generate(&env), &1u64);
 
-        env.mock_all_auths();
-        client.init(&admin, &reward_contract);
+        let (client, admin) = setup_atomic_with_reward_abi(&env, 3);
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::IncompatibleReward))
+        );
+        assert!(client.badges_of(&user).is_empty());
 
-        (client, admin, reward_contract)
+        // A standard token cannot report a version, so it fails the check.
+        let (client, admin) = setup_atomic_with_reward_abi(&env, 2);
+        setup_reward_token(&env, &client, &admin, 100);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::IncompatibleReward))
+        );
     }
 
-    // ------------------------------------------------------------------
-    // 1. init
-    // ------------------------------------------------------------------
-
     #[test]
-    fn test_init_rejects_reinit() {
+    fn test_atomic_settlement_requires_reward_token() {
         let env = Env::default();
-        let (client, admin, reward_contract) = setup(&env);
+        let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let result = client.try_init(&admin, &reward_contract);
-        assert!(result.is_err());
+        assert_eq!(client.settlement_mode(), SettlementMode::EventDriven);
+        client.pause(&admin);
+        assert_eq!(
+            client.try_set_settlement_mode(&admin, &SettlementMode::Atomic),
+            Err(Ok(Error::RewardTokenNotSet))
+        );
     }
 
     #[test]
-    fn test_uninit_privileged_calls_rejected() {
+    fn test_settlement_mode_changes_only_while_paused() {
         let env = Env::default();
-        let contract_id = env.register(AchievementBadge, ());
-        let client = AchievementBadgeClient::new(&env, &contract_id);
+        let (client, admin, _) = setup(&env);
         env.mock_all_auths();
+        setup_reward_token(&env, &client, &admin, 100);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 194), &10i128);
 
-        let admin = Address::generate(&env);
+        assert_eq!(
+            client.try_set_settlement_mode(&admin, &SettlementMode::Atomic),
+            Err(Ok(Error::NotPaused))
+        );
+
+        client.pause(&admin);
+        assert!(client.is_paused());
+        assert_eq!(client.try_pause(&admin), Err(Ok(Error::AlreadyPaused)));
         let user = Address::generate(&env);
-        let hash = make_hash(&env, 1);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::ContractPaused))
+        );
+        assert_eq!(
+            client.try_renounce_admin(&admin),
+            Err(Ok(Error::ContractPaused))
+        );
 
-        assert!(client.try_define_badge(&admin, &1u64, &hash, &0i128).is_err());
-        assert!(client.try_evaluate_user(&admin, &user, &1u64).is_err());
-        assert!(client.try_award_badge(&admin, &user, &1u64).is_err());
+        client.set_settlement_mode(&admin, &SettlementMode::Atomic);
+        client.unpause(&admin);
+        assert_eq!(client.try_unpause(&admin), Err(Ok(Error::NotPaused)));
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.settlement_mode(), SettlementMode::Atomic);
     }
 
-    // ------------------------------------------------------------------
-    // 2. define_badge
-    // ------------------------------------------------------------------
-
     #[test]
-    fn test_define_badge_success() {
+    fn test_vested_reward_claims_linearly_after_cliff() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
+        let token = setup_reward_token(&env, &client, &admin, 1_000);
 
-        let hash = make_hash(&env, 42);
-        client.define_badge(&admin, &1u64, &hash, &500i128);
-        // No panic = success
+        client.define_badge(&admin, &1u64, &make_hash(&env, 192), &1_000i128);
+        client.set_badge_vesting(&admin, &1u64, &100u32, &1_000u32);
+        assert_eq!(client.badge_vesting(&1u64), Some((100, 1_000)));
+
+        env.ledger().set_sequence_number(500);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.claimable(&user), 0);
+        assert_eq!(client.vesting_of(&user, &1u64).unwrap().start_ledger, 500);
+
+        env.ledger().set_sequence_number(599);
+        assert_eq!(
+            client.try_claim_vested(&user, &1u64),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        env.ledger().set_sequence_number(750);
+        assert_eq!(client.claim_vested(&user, &1u64), 250);
+        assert_eq!(
+            client.try_claim_vested(&user, &1u64),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        env.ledger().set_sequence_number(5_000);
+        assert_eq!(client.claim_vested(&user, &1u64), 750);
+        assert_eq!(
+            soroban_sdk::token::TokenClient::new(&env, &token).balance(&user),
+            1_000
+        );
+        assert_eq!(client.vesting_of(&user, &1u64).unwrap().claimed, 1_000);
     }
 
     #[test]
-    fn test_define_badge_duplicate_rejected() {
+    fn test_set_badge_vesting_validates_schedule() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 1);
-        client.define_badge(&admin, &10u64, &hash, &0i128);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 193), &10i128);
+        assert_eq!(
+            client.try_set_badge_vesting(&admin, &1u64, &0u32, &0u32),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(
+            client.try_set_badge_vesting(&admin, &1u64, &11u32, &10u32),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(
+            client.try_set_badge_vesting(&admin, &2u64, &0u32, &10u32),
+            Err(Ok(Error::BadgeNotFound))
+        );
+        assert_eq!(client.badge_vesting(&1u64), None);
 
-        let result = client.try_define_badge(&admin, &10u64, &hash, &0i128);
-        assert!(result.is_err());
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_claim_vested(&user, &1u64),
+            Err(Ok(Error::NothingToClaim))
+        );
     }
 
     #[test]
-    fn test_define_badge_negative_reward_rejected() {
+    fn test_claim_rewards_requires_reward_token() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 2);
-        let result = client.try_define_badge(&admin, &1u64, &hash, &-1i128);
-        assert!(result.is_err());
+        client.define_badge(&admin, &1u64, &make_hash(&env, 185), &5i128);
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+
+        assert_eq!(
+            client.try_claim_rewards(&user),
+            Err(Ok(Error::RewardTokenNotSet))
+        );
+        assert_eq!(client.claimable(&user), 5);
+        assert_eq!(client.reward_token(), None);
     }
 
+    // ------------------------------------------------------------------
+    // 64-byte criteria hashes
+    // ------------------------------------------------------------------
+
     #[test]
-    fn test_define_badge_non_admin_rejected() {
+    fn test_define_badge_64_stores_long_hash() {
         let env = Env::default();
-        let (client, _, _) = setup(&env);
+        let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let non_admin = Address::generate(&env);
-        let hash = make_hash(&env, 3);
-        let result = client.try_define_badge(&non_admin, &1u64, &hash, &0i128);
-        assert!(result.is_err());
-    }
+        let hash_64 = BytesN::from_array(&env, &[9u8; 64]);
+        client.define_badge_64(&admin, &1u64, &hash_64, &3i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 190), &0i128);
 
-    // ------------------------------------------------------------------
-    // 3. evaluate_user
-    // ------------------------------------------------------------------
+        assert_eq!(client.criteria_hash_64(&1u64), Some(hash_64.clone()));
+        assert_eq!(client.criteria_hash_64(&2u64), None);
+
+        let digest: BytesN<32> = env.crypto().sha256(&hash_64.into()).into();
+        assert_eq!(
+            client.badges_by_hash(&digest),
+            soroban_sdk::vec![&env, 1u64]
+        );
+
+        let user = Address::generate(&env);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(client.claimable(&user), 3);
+        client.check_invariants(&0u32, &MAX_PAGE_SIZE, &soroban_sdk::vec![&env]);
+    }
 
     #[test]
-    fn test_evaluate_user_success() {
+    fn test_verify_criteria_dispatches_on_hash_length() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 5);
-        client.define_badge(&admin, &1u64, &hash, &0i128);
+        let hash_64 = BytesN::from_array(&env, &[9u8; 64]);
+        client.define_badge_64(&admin, &1u64, &hash_64, &0i128);
 
-        let user = Address::generate(&env);
-        client.evaluate_user(&admin, &user, &1u64);
-        // No panic = success
+        assert!(client.verify_criteria(&1u64, &Bytes::from(hash_64)));
+        assert!(!client.verify_criteria(&1u64, &Bytes::from_array(&env, &[9u8; 32])));
+        assert_eq!(
+            client.try_define_badge_64(
+                &admin,
+                &1u64,
+                &BytesN::from_array(&env, &[1u8; 64]),
+                &0i128
+            ),
+            Err(Ok(Error::BadgeAlreadyExists))
+        );
     }
 
+    // ------------------------------------------------------------------
+    // Overflow guards
+    // ------------------------------------------------------------------
+
     #[test]
-    fn test_evaluate_user_undefined_badge_rejected() {
+    fn test_claimable_overflow_leaves_badge_unawarded() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
+        client.define_badge(&admin, &1u64, &make_hash(&env, 200), &i128::MAX);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 201), &1i128);
+
         let user = Address::generate(&env);
-        // badge 999 not defined
-        let result = client.try_evaluate_user(&admin, &user, &999u64);
-        assert!(result.is_err());
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &2u64),
+            Err(Ok(Error::Overflow))
+        );
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+        assert_eq!(client.claimable(&user), i128::MAX);
     }
 
     #[test]
-    fn test_evaluate_user_non_admin_rejected() {
+    fn test_holder_count_overflow_is_rejected() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 6);
-        client.define_badge(&admin, &1u64, &hash, &0i128);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 202), &0i128);
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::HolderCount(1), &u32::MAX);
+        });
 
-        let non_admin = Address::generate(&env);
         let user = Address::generate(&env);
-        let result = client.try_evaluate_user(&non_admin, &user, &1u64);
-        assert!(result.is_err());
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::Overflow))
+        );
+        assert_eq!(client.holder_count(&1u64), u32::MAX);
     }
 
     // ------------------------------------------------------------------
-    // 4. award_badge
+    // Tournament-scoped badges
     // ------------------------------------------------------------------
 
+    #[contract]
+    struct MockTournament;
+
+    #[contractimpl]
+    impl MockTournament {
+        pub fn join(env: Env, id: u64, player: Address) {
+            env.storage().persistent().set(&(id, player), &true);
+        }
+
+        pub fn is_joined(env: Env, id: u64, player: Address) -> bool {
+            env.storage().persistent().has(&(id, player))
+        }
+    }
+
+    /// Define badge 1 scoped to tournament 42 and badge 2 as global.
+    fn setup_scoped(
+        env: &Env,
+    ) -> (
+        AchievementBadgeClient<'_>,
+        Address,
+        MockTournamentClient<'_>,
+    ) {
+        let (client, admin, _) = setup(env);
+        env.mock_all_auths();
+
+        let definition = BadgeDefinition {
+            scope: BadgeScope::Tournament(42),
+            ..BadgeDefinition::new(make_hash(env, 230), 0)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+        client.define_badge(&admin, &2u64, &make_hash(env, 231), &0i128);
+
+        let tournaments = MockTournamentClient::new(env, &env.register(MockTournament, ()));
+        (client, admin, tournaments)
+    }
+
     #[test]
-    fn test_award_badge_success() {
+    fn test_scoped_badge_requires_tournament_participation() {
         let env = Env::default();
-        let (client, admin, _) = setup(&env);
-        env.mock_all_auths();
+        let (client, admin, tournaments) = setup_scoped(&env);
+        let user = Address::generate(&env);
+
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::TournamentContractNotSet))
+        );
+        client.set_tournament_contract(&admin, &tournaments.address);
+        assert_eq!(
+            client.tournament_contract(),
+            Some(tournaments.address.clone())
+        );
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::PlayerNotJoined))
+        );
 
-        let hash = make_hash(&env, 7);
-        client.define_badge(&admin, &1u64, &hash, &100i128);
+        tournaments.join(&7u64, &user);
+        assert_eq!(
+            client.try_award_badge(&admin, &user, &1u64),
+            Err(Ok(Error::PlayerNotJoined))
+        );
 
-        let user = Address::generate(&env);
+        tournaments.join(&42u64, &user);
         client.award_badge(&admin, &user, &1u64);
-
-        let badges = client.badges_of(&user);
-        assert_eq!(badges.len(), 1);
-        assert_eq!(badges.get(0).unwrap(), 1u64);
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
     }
 
     #[test]
-    fn test_award_badge_undefined_badge_rejected() {
+    fn test_badges_of_tournament_filters_by_scope() {
         let env = Env::default();
-        let (client, admin, _) = setup(&env);
-        env.mock_all_auths();
+        let (client, admin, tournaments) = setup_scoped(&env);
+        client.set_tournament_contract(&admin, &tournaments.address);
 
         let user = Address::generate(&env);
-        let result = client.try_award_badge(&admin, &user, &999u64);
-        assert!(result.is_err());
+        tournaments.join(&42u64, &user);
+        client.award_badge(&admin, &user, &2u64);
+        client.award_badge(&admin, &user, &1u64);
+
+        assert_eq!(
+            client.badges_of_tournament(&user, &42u64),
+            soroban_sdk::vec![&env, 1u64]
+        );
+        assert_eq!(
+            client.badges_of_tournament(&user, &7u64),
+            soroban_sdk::vec![&env]
+        );
+
+        client.set_private(&user, &true);
+        assert_eq!(
+            client.badges_of_tournament(&user, &42u64),
+            soroban_sdk::vec![&env]
+        );
     }
 
-    #[test]
-    fn test_award_badge_duplicate_rejected() {
-        let env = Env::default();
-        let (client, admin, _) = setup(&env);
-        env.mock_all_auths();
+    // ------------------------------------------------------------------
+    // Award subscribers
+    // ------------------------------------------------------------------
 
-        let hash = make_hash(&env, 8);
-        client.define_badge(&admin, &1u64, &hash, &0i128);
+    #[contract]
+    struct MockSubscriber;
 
-        let user = Address::generate(&env);
-        client.award_badge(&admin, &user, &1u64);
+    #[contractimpl]
+    impl MockSubscriber {
+        pub fn set_failing(env: Env, failing: bool) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("fail"), &failing);
+        }
 
-        let result = client.try_award_badge(&admin, &user, &1u64);
-        assert!(result.is_err());
+        pub fn on_badge_awarded(env: Env, user: Address, badge_id: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(user, badge_id));
+            if env
+                .storage()
+                .instance()
+                .get(&symbol_short!("fail"))
+                .unwrap_or(false)
+            {
+                panic!("subscriber failure");
+            }
+        }
+
+        pub fn last(env: Env) -> Option<(Address, u64)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
     }
 
     #[test]
-    fn test_award_badge_non_admin_rejected() {
+    fn test_subscriber_notified_after_award() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
+        let subscriber = MockSubscriberClient::new(&env, &env.register(MockSubscriber, ()));
+        let user = Address::generate(&env);
 
-        let hash = make_hash(&env, 9);
-        client.define_badge(&admin, &2u64, &hash, &0i128);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 240), &0i128);
+        client.set_badge_subscriber(&admin, &1u64, &Some(subscriber.address.clone()));
+        assert_eq!(
+            client.badge_subscriber(&1u64),
+            Some(subscriber.address.clone())
+        );
 
-        let non_admin = Address::generate(&env);
-        let user = Address::generate(&env);
-        let result = client.try_award_badge(&non_admin, &user, &2u64);
-        assert!(result.is_err());
+        client.award_badge(&admin, &user, &1u64);
+        assert!(env.events().all().events().contains(
+            &SubscriberNotified {
+                user: user.clone(),
+                badge_id: 1,
+                subscriber: subscriber.address.clone(),
+                success: true,
+            }
+            .to_xdr(&env, &client.address)
+        ));
+        assert_eq!(subscriber.last(), Some((user.clone(), 1u64)));
     }
 
     #[test]
-    fn test_award_multiple_badges_to_same_user() {
+    fn test_failing_subscriber_does_not_revert_award() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
-
+        let subscriber = MockSubscriberClient::new(&env, &env.register(MockSubscriber, ()));
+        subscriber.set_failing(&true);
         let user = Address::generate(&env);
 
-        for id in 1u64..=3 {
-            let hash = make_hash(&env, id as u8);
-            client.define_badge(&admin, &id, &hash, &0i128);
-            client.award_badge(&admin, &user, &id);
-        }
+        client.define_badge(&admin, &1u64, &make_hash(&env, 241), &0i128);
+        client.set_badge_subscriber(&admin, &1u64, &Some(subscriber.address.clone()));
 
-        let badges = client.badges_of(&user);
-        assert_eq!(badges.len(), 3);
-        assert_eq!(badges.get(0).unwrap(), 1u64);
-        assert_eq!(badges.get(1).unwrap(), 2u64);
-        assert_eq!(badges.get(2).unwrap(), 3u64);
+        client.award_badge(&admin, &user, &1u64);
+        assert!(env.events().all().events().contains(
+            &SubscriberNotified {
+                user: user.clone(),
+                badge_id: 1,
+                subscriber: subscriber.address.clone(),
+                success: false,
+            }
+            .to_xdr(&env, &client.address)
+        ));
+        assert_eq!(client.badges_of(&user), soroban_sdk::vec![&env, 1u64]);
+        assert_eq!(subscriber.last(), None);
     }
 
     #[test]
-    fn test_same_badge_different_users() {
+    fn test_cleared_subscriber_is_not_called() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
+        let subscriber = MockSubscriberClient::new(&env, &env.register(MockSubscriber, ()));
+        let user = Address::generate(&env);
 
-        let hash = make_hash(&env, 10);
-        client.define_badge(&admin, &1u64, &hash, &50i128);
-
-        let user_a = Address::generate(&env);
-        let user_b = Address::generate(&env);
-
-        client.award_badge(&admin, &user_a, &1u64);
-        client.award_badge(&admin, &user_b, &1u64);
+        client.define_badge(&admin, &1u64, &make_hash(&env, 242), &0i128);
+        client.set_badge_subscriber(&admin, &1u64, &Some(subscriber.address.clone()));
+        client.set_badge_subscriber(&admin, &1u64, &None);
+        assert_eq!(client.badge_subscriber(&1u64), None);
+        assert_eq!(
+            client.try_set_badge_subscriber(&admin, &9u64, &None),
+            Err(Ok(Error::BadgeNotFound))
+        );
 
-        assert_eq!(client.badges_of(&user_a).len(), 1);
-        assert_eq!(client.badges_of(&user_b).len(), 1);
+        client.award_badge(&admin, &user, &1u64);
+        assert_eq!(subscriber.last(), None);
     }
 
     // ------------------------------------------------------------------
-    // 5. badges_of
+    // storage_stats
     // ------------------------------------------------------------------
 
     #[test]
-    fn test_badges_of_returns_empty_for_new_user() {
+    fn test_storage_stats_aggregates_counters() {
         let env = Env::default();
-        let (client, _, _) = setup(&env);
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
 
-        let user = Address::generate(&env);
-        let badges = client.badges_of(&user);
-        assert_eq!(badges.len(), 0);
+        assert_eq!(
+            client.storage_stats(),
+            StorageStats {
+                badges: 0,
+                meta_badges: 0,
+                holdings: 0,
+                awards_issued: 0
+            }
+        );
+
+        client.define_badge(&admin, &1u64, &make_hash(&env, 1), &0i128);
+        client.define_badge(&admin, &2u64, &make_hash(&env, 2), &0i128);
+        client.define_meta_badge(&admin, &3u64, &soroban_sdk::vec![&env, 1u64, 2u64], &0i128);
+
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        client.award_badge(&admin, &alice, &1u64);
+        client.award_badge(&admin, &alice, &2u64);
+        client.award_badge(&admin, &bob, &1u64);
+        client.revoke_badge_batch(&admin, &soroban_sdk::vec![&env, bob.clone()], &1u64);
+
+        assert_eq!(
+            client.storage_stats(),
+            StorageStats {
+                badges: 3,
+                meta_badges: 1,
+                holdings: 2,
+                awards_issued: 3
+            }
+        );
+
+        client.delete_badge_definition(&admin, &3u64);
+        client.award_badge(&admin, &bob, &2u64);
+        assert_eq!(
+            client.storage_stats(),
+            StorageStats {
+                badges: 2,
+                meta_badges: 0,
+                holdings: 3,
+                awards_issued: 4
+            }
+        );
     }
 
     // ------------------------------------------------------------------
-    // 6. Full lifecycle
+    // awards_in_range
     // ------------------------------------------------------------------
 
     #[test]
-    fn test_full_lifecycle() {
+    fn test_awards_in_range_filters_by_ledger() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
         let user = Address::generate(&env);
+        for (badge_id, ledger) in [(1u64, 100u32), (2, 200), (3, 300)] {
+            client.define_badge(
+                &admin,
+                &badge_id,
+                &make_hash(&env, 210 + badge_id as u8),
+                &0i128,
+            );
+            env.ledger().set_sequence_number(ledger);
+            client.award_badge(&admin, &user, &badge_id);
+        }
 
-        // Define two badges.
-        client.define_badge(&admin, &1u64, &make_hash(&env, 11), &200i128);
-        client.define_badge(&admin, &2u64, &make_hash(&env, 12), &0i128);
-
-        // Evaluate user against badge 1 (just auditing).
-        client.evaluate_user(&admin, &user, &1u64);
-
-        // Award badge 1.
-        client.award_badge(&admin, &user, &1u64);
-
-        // Evaluate and award badge 2.
-        client.evaluate_user(&admin, &user, &2u64);
-        client.award_badge(&admin, &user, &2u64);
+        assert_eq!(
+            client.awards_in_range(&user, &100u32, &200u32),
+            soroban_sdk::vec![&env, 1u64, 2u64]
+        );
+        assert_eq!(
+            client.awards_in_range(&user, &201u32, &300u32),
+            soroban_sdk::vec![&env, 3u64]
+        );
+        assert_eq!(
+            client.awards_in_range(&user, &301u32, &400u32),
+            soroban_sdk::vec![&env]
+        );
+        assert_eq!(
+            client.awards_in_range(&user, &300u32, &300u32),
+            soroban_sdk::vec![&env, 3u64]
+        );
+    }
 
-        let badges = client.badges_of(&user);
-        assert_eq!(badges.len(), 2);
-        assert_eq!(badges.get(0).unwrap(), 1u64);
-        assert_eq!(badges.get(1).unwrap(), 2u64);
+    #[test]
+    fn test_awards_in_range_rejects_inverted_range() {
+        let env = Env::default();
+        let (client, _, _) = setup(&env);
 
-        // Duplicate award must fail.
-        assert!(client.try_award_badge(&admin, &user, &1u64).is_err());
+        let user = Address::generate(&env);
+        assert_eq!(
+            client.try_awards_in_range(&user, &5u32, &4u32),
+            Err(Ok(Error::InvalidInput))
+        );
     }
 
     // ------------------------------------------------------------------
-    // 7. get_badge_summary
+    // Badge names
     // ------------------------------------------------------------------
 
     #[test]
-    fn test_get_badge_summary_known_badge_without_metadata() {
+    fn test_badge_name_is_stored_and_emitted() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 20);
-        client.define_badge(&admin, &5u64, &hash, &100i128);
+        let name = String::from_str(&env, "First Blood");
+        let definition = BadgeDefinition {
+            name: Some(name.clone()),
+            ..BadgeDefinition::new(make_hash(&env, 220), 0)
+        };
+        client.define_badge_full(&admin, &1u64, &definition);
+        assert_eq!(
+            env.events().all(),
+            [BadgeDefined {
+                badge_id: 1,
+                criteria_hash: make_hash(&env, 220),
+                reward: 0,
+                reward_token_decimals: 7,
+                name: Some(name.clone()),
+            }
+            .to_xdr(&env, &client.address)]
+        );
 
-        let summary = client.get_badge_summary(&5u64);
-        assert!(summary.found);
-        assert_eq!(summary.badge_id, 5u64);
-        assert_eq!(summary.reward, 100i128);
-        assert_eq!(summary.criteria_hash, hash);
-        // Metadata not yet set — empty strings expected.
-        assert_eq!(summary.title, soroban_sdk::String::from_str(&env, ""));
+        client.define_badge(&admin, &2u64, &make_hash(&env, 221), &0i128);
+        assert_eq!(client.badge_name(&1u64), Some(name));
+        assert_eq!(client.badge_name(&2u64), None);
+        assert_eq!(client.badge_name(&3u64), None);
     }
 
     #[test]
-    fn test_get_badge_summary_known_badge_with_metadata() {
+    fn test_badge_name_length_is_bounded() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 21);
-        client.define_badge(&admin, &6u64, &hash, &0i128);
-
-        client.set_badge_metadata(
-            &admin,
-            &6u64,
-            &soroban_sdk::String::from_str(&env, "First Win"),
-            &soroban_sdk::String::from_str(&env, "Win your first game"),
-            &soroban_sdk::String::from_str(&env, "Win one ranked match"),
-        );
-
-        let summary = client.get_badge_summary(&6u64);
-        assert!(summary.found);
-        assert_eq!(summary.title, soroban_sdk::String::from_str(&env, "First Win"));
+        let long = String::from_bytes(&env, &[b'a'; 65]);
+        let definition = BadgeDefinition {
+            name: Some(long),
+            ..BadgeDefinition::new(make_hash(&env, 222), 0)
+        };
         assert_eq!(
-            summary.description,
-            soroban_sdk::String::from_str(&env, "Win your first game")
+            client.try_define_badge_full(&admin, &1u64, &definition),
+            Err(Ok(Error::InvalidInput))
         );
+
+        let empty = BadgeDefinition {
+            name: Some(String::from_str(&env, "")),
+            ..BadgeDefinition::new(make_hash(&env, 222), 0)
+        };
         assert_eq!(
-            summary.award_rules,
-            soroban_sdk::String::from_str(&env, "Win one ranked match")
+            client.try_define_badge_full(&admin, &1u64, &empty),
+            Err(Ok(Error::InvalidInput))
         );
-    }
-
-    #[test]
-    fn test_get_badge_summary_unknown_badge_returns_not_found() {
-        let env = Env::default();
-        let (client, _, _) = setup(&env);
 
-        let summary = client.get_badge_summary(&9999u64);
-        assert!(!summary.found);
-        assert_eq!(summary.badge_id, 9999u64);
-        assert_eq!(summary.reward, 0i128);
+        let max = BadgeDefinition {
+            name: Some(String::from_bytes(&env, &[b'a'; 64])),
+            ..BadgeDefinition::new(make_hash(&env, 222), 0)
+        };
+        client.define_badge_full(&admin, &1u64, &max);
     }
 
+    // ------------------------------------------------------------------
+    // badges_with_defs
+    // ------------------------------------------------------------------
+
     #[test]
-    fn test_set_badge_metadata_requires_badge_defined() {
+    fn test_badges_with_defs_pages_holdings() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        // Badge 42 not defined — should fail.
-        let result = client.try_set_badge_metadata(
-            &admin,
-            &42u64,
-            &soroban_sdk::String::from_str(&env, "Title"),
-            &soroban_sdk::String::from_str(&env, "Desc"),
-            &soroban_sdk::String::from_str(&env, "Rules"),
+        let user = Address::generate(&env);
+        for badge_id in 1u64..=3 {
+            let hash = make_hash(&env, 230 + badge_id as u8);
+            client.define_badge(&admin, &badge_id, &hash, &(badge_id as i128));
+            client.award_badge(&admin, &user, &badge_id);
+        }
+
+        let page = client.badges_with_defs(&user, &1u32, &10u32);
+        assert_eq!(page.len(), 2);
+        assert_eq!(
+            page.get(0).unwrap(),
+            (2u64, BadgeDefinition::new(make_hash(&env, 232), 2))
         );
-        assert!(result.is_err());
+        assert_eq!(page.get(1).unwrap().0, 3);
+        assert_eq!(
+            client
+                .badges_with_defs(&user, &0u32, &1u32)
+                .get(0)
+                .unwrap()
+                .0,
+            1
+        );
+        assert_eq!(client.badges_with_defs(&user, &5u32, &10u32).len(), 0);
     }
 
-    // ------------------------------------------------------------------
-    // 8. get_claim_status
-    // ------------------------------------------------------------------
-
     #[test]
-    fn test_get_claim_status_claimed_user() {
+    fn test_missing_badges_pages_unheld_badges() {
         let env = Env::default();
         let (client, admin, _) = setup(&env);
         env.mock_all_auths();
 
-        let hash = make_hash(&env, 30);
-        client.define_badge(&admin, &7u64, &hash, &0i128);
-
         let user = Address::generate(&env);
-        client.award_badge(&admin, &user, &7u64);
+        for badge_id in 1u64..=4 {
+            client.define_badge(
+                &admin,
+                &badge_id,
+                &make_hash(&env, 240 + badge_id as u8),
+                &0i128,
+            );
+        }
+        assert_eq!(
+            client.missing_badges(&user, &0u32, &10u32),
+            soroban_sdk::vec![&env, 1u64, 2, 3, 4]
+        );
 
-        let snapshot = client.get_claim_status(&user, &7u64);
-        assert!(snapshot.badge_found);
-        assert!(snapshot.claimed);
-        assert_eq!(snapshot.badge_id, 7u64);
+        client.award_badge(&admin, &user, &2u64);
+        client.award_badge(&admin, &user, &3u64);
+        assert_eq!(
+            client.missing_badges(&user, &0u32, &10u32),
+            soroban_sdk::vec![&env, 1u64, 4]
+        );
+        assert_eq!(
+            client.missing_badges(&user, &0u32, &2u32),
+            soroban_sdk::vec![&env, 1u64]
+        );
+        assert_eq!(
+            client.missing_badges(&user, &2u32, &2u32),
+            soroban_sdk::vec![&env, 4u64]
+        );
+        assert_eq!(client.missing_badges(&user, &9u32, &2u32).len(), 0);
     }
 
     #[test]
-    fn test_get_claim_status_unclaimed_user() {
+    fn test_missing_badges_caps_limit() {
         let env = Env::default();
-        let (client, admin, _) = setup(&env);
-        env.mock_all_auths();
-
-        let hash = make_hash(&env, 31);
-        client.define_badge(&admin, &8u64, &hash, &0i128);
+        let (client, _, _) = setup(&env);
 
+        let ids: Vec<u64> = Vec::from_iter(&env, 1u64..=60);
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(&DataKey::BadgeIndex, &ids);
+        });
         let user = Address::generate(&env);
-        // User has not been awarded the badge.
-        let snapshot = client.get_claim_status(&user, &8u64);
-        assert!(snapshot.badge_found);
-        assert!(!snapshot.claimed);
+        assert_eq!(
+            client.missing_badges(&user, &0u32, &100u32).len(),
+            MAX_PAGE_SIZE
+        );
     }
 
     #[test]
-    fn test_get_claim_status_unknown_badge() {
+    fn test_badges_with_defs_skips_missing_and_caps_limit() {
         let env = Env::default();
-        let (client, _, _) = setup(&env);
+        let (client, admin, _) = setup(&env);
+        env.mock_all_auths();
 
         let user = Address::generate(&env);
-        let snapshot = client.get_claim_status(&user, &9999u64);
-        assert!(!snapshot.badge_found);
-        assert!(!snapshot.claimed);
+        let held: Vec<u64> = Vec::from_iter(&env, 1u64..=60);
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::UserBadges(user.clone()), &held);
+        });
+        for badge_id in 1u64..=60 {
+            if badge_id != 2 {
+                client.define_badge(&admin, &badge_id, &make_hash(&env, badge_id as u8), &0i128);
+            }
+        }
+
+        let page = client.badges_with_defs(&user, &0u32, &u32::MAX);
+        assert_eq!(page.len(), MAX_PAGE_SIZE - 1);
+        assert_eq!(page.get(1).unwrap().0, 3);
     }
 }